@@ -0,0 +1,151 @@
+use opencv::core::{Mat, MatTraitConst};
+use opencv::prelude::*;
+use std::f64::consts::PI;
+
+const BASE83_CHARS: &[u8] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz#$%*+,-.:;=?@[]^_{|}~";
+
+/// Encode a BGR `Mat` (e.g. the output of `extract_first_frame`, or an
+/// inpainting `result`) as a BlurHash string.
+///
+/// `components_x`/`components_y` control the number of DCT basis functions
+/// per axis (1-9); callers typically use a small grid like 4x3 so the
+/// resulting string stays compact.
+pub fn encode(mat: &Mat, components_x: u32, components_y: u32) -> Result<String, String> {
+    if !(1..=9).contains(&components_x) || !(1..=9).contains(&components_y) {
+        return Err("BlurHash components must be between 1 and 9".to_string());
+    }
+
+    let width = mat.cols();
+    let height = mat.rows();
+    if width <= 0 || height <= 0 {
+        return Err("Cannot hash an empty image".to_string());
+    }
+
+    // Decode the BGR Mat into linear-light RGB samples once up front.
+    let mut linear = vec![[0.0f64; 3]; (width * height) as usize];
+    for y in 0..height {
+        for x in 0..width {
+            let pixel = mat
+                .at_2d::<opencv::core::Vec3b>(y, x)
+                .map_err(|e| format!("Failed to read pixel ({}, {}): {}", x, y, e))?;
+            let idx = (y * width + x) as usize;
+            // OpenCV stores BGR order.
+            linear[idx][0] = srgb_to_linear(pixel[2]); // R
+            linear[idx][1] = srgb_to_linear(pixel[1]); // G
+            linear[idx][2] = srgb_to_linear(pixel[0]); // B
+        }
+    }
+
+    let mut factors = Vec::with_capacity((components_x * components_y) as usize);
+    for j in 0..components_y {
+        for i in 0..components_x {
+            let normalization = if i == 0 && j == 0 { 1.0 } else { 2.0 };
+            let mut r = 0.0;
+            let mut g = 0.0;
+            let mut b = 0.0;
+            for y in 0..height {
+                for x in 0..width {
+                    let basis = (PI * i as f64 * x as f64 / width as f64).cos()
+                        * (PI * j as f64 * y as f64 / height as f64).cos();
+                    let sample = linear[(y * width + x) as usize];
+                    r += basis * sample[0];
+                    g += basis * sample[1];
+                    b += basis * sample[2];
+                }
+            }
+            let scale = normalization / (width as f64 * height as f64);
+            factors.push([r * scale, g * scale, b * scale]);
+        }
+    }
+
+    Ok(assemble(&factors, components_x, components_y))
+}
+
+fn assemble(factors: &[[f64; 3]], components_x: u32, components_y: u32) -> String {
+    let dc = factors[0];
+    let ac = &factors[1..];
+
+    let mut result = String::new();
+
+    let size_flag = (components_x - 1) + (components_y - 1) * 9;
+    result.push_str(&encode_base83(size_flag as u64, 1));
+
+    let max_ac = ac
+        .iter()
+        .flat_map(|c| c.iter())
+        .fold(0.0f64, |max, &v| max.max(v.abs()));
+
+    let quantized_max_value = if ac.is_empty() {
+        0
+    } else {
+        ((max_ac * 166.0 - 0.5).floor() as i64).clamp(0, 82) as u64
+    };
+    result.push_str(&encode_base83(quantized_max_value, 1));
+
+    let actual_max_value = (quantized_max_value as f64 + 1.0) / 166.0;
+
+    result.push_str(&encode_base83(encode_dc(dc), 4));
+
+    for component in ac {
+        result.push_str(&encode_base83(
+            encode_ac(*component, actual_max_value),
+            2,
+        ));
+    }
+
+    result
+}
+
+fn encode_dc(value: [f64; 3]) -> u64 {
+    let r = linear_to_srgb(value[0]) as u64;
+    let g = linear_to_srgb(value[1]) as u64;
+    let b = linear_to_srgb(value[2]) as u64;
+    (r << 16) + (g << 8) + b
+}
+
+fn encode_ac(value: [f64; 3], maximum_value: f64) -> u64 {
+    let quantize = |v: f64| -> u64 {
+        let normalized = sign_pow(v / maximum_value, 0.5);
+        ((normalized * 9.0 + 9.5).floor() as i64).clamp(0, 18) as u64
+    };
+
+    let r = quantize(value[0]);
+    let g = quantize(value[1]);
+    let b = quantize(value[2]);
+
+    r * 19 * 19 + g * 19 + b
+}
+
+fn sign_pow(value: f64, exponent: f64) -> f64 {
+    value.signum() * value.abs().powf(exponent)
+}
+
+fn srgb_to_linear(value: u8) -> f64 {
+    let v = value as f64 / 255.0;
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f64) -> u8 {
+    let v = value.clamp(0.0, 1.0);
+    let encoded = if v <= 0.0031308 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    };
+    (encoded * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+fn encode_base83(mut value: u64, length: usize) -> String {
+    let mut digits = vec![0u8; length];
+    for i in (0..length).rev() {
+        let digit = (value % 83) as usize;
+        digits[i] = BASE83_CHARS[digit];
+        value /= 83;
+    }
+    String::from_utf8(digits).unwrap_or_default()
+}
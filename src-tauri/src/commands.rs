@@ -1,12 +1,18 @@
+use crate::blurhash;
+use crate::errors::ProcessError;
 use crate::gemini_client::GeminiClient;
-use crate::image_processor::{self, RemovalOptions, WatermarkRegion};
-use crate::video_processor::{self, VideoInfo, VideoProgress, VideoProcessResult};
+use crate::image_processor::{self, MaskSpec, RemovalOptions, ThumbnailSize, WatermarkRegion};
+use crate::validate::{self, MediaLimits};
+use crate::video_processor::{self, SpriteSheet, VideoInfo, VideoOutputOptions, VideoProgress, VideoProcessResult};
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use opencv::prelude::{MatTraitConst, VectorToVec};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
 use tauri_plugin_store::StoreExt;
+use tokio::sync::Semaphore;
 use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -15,6 +21,28 @@ pub struct ProcessResult {
     pub base64_preview: Option<String>,
     pub original_size: u64,
     pub processed_size: u64,
+    pub metadata_stripped: bool,
+    pub blurhash: Option<String>,
+}
+
+/// Number of DCT basis functions used when generating preview BlurHashes.
+const BLURHASH_COMPONENTS_X: u32 = 4;
+const BLURHASH_COMPONENTS_Y: u32 = 3;
+
+/// Decode image bytes and compute a BlurHash placeholder, swallowing
+/// failures since the hash is a nice-to-have for the gallery rather than
+/// something that should fail the whole processing command.
+fn compute_blurhash(bytes: &[u8]) -> Option<String> {
+    use opencv::core::Vector;
+    use opencv::imgcodecs;
+
+    let buf = Vector::<u8>::from_slice(bytes);
+    let img = imgcodecs::imdecode(&buf, imgcodecs::IMREAD_COLOR).ok()?;
+    if img.empty() {
+        return None;
+    }
+
+    blurhash::encode(&img, BLURHASH_COMPONENTS_X, BLURHASH_COMPONENTS_Y).ok()
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -25,26 +53,30 @@ pub struct ImageInfo {
 }
 
 /// Get temporary directory for storing processed images
-fn get_temp_dir() -> Result<PathBuf, String> {
+fn get_temp_dir() -> Result<PathBuf, ProcessError> {
     let temp_dir = std::env::temp_dir().join("watermark-remover");
     if !temp_dir.exists() {
         fs::create_dir_all(&temp_dir)
-            .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+            .map_err(|e| ProcessError::Io(format!("Failed to create temp directory: {}", e)))?;
     }
     Ok(temp_dir)
 }
 
 #[tauri::command]
 pub async fn remove_watermark(
+    app: tauri::AppHandle,
     image_path: String,
-    region: WatermarkRegion,
+    mask_spec: MaskSpec,
     options: Option<RemovalOptions>,
-) -> Result<ProcessResult, String> {
+) -> Result<ProcessResult, ProcessError> {
     let options = options.unwrap_or_default();
 
+    let limits = get_stored_media_limits(&app)?;
+    validate::validate_image(&image_path, &limits)?;
+
     // Get original file size
     let original_size = fs::metadata(&image_path)
-        .map_err(|e| format!("Failed to get original file size: {}", e))?
+        .map_err(|e| ProcessError::Io(format!("Failed to get original file size: {}", e)))?
         .len();
 
     // Generate unique output filename
@@ -64,12 +96,19 @@ pub async fn remove_watermark(
     let output_path_str = output_path.to_string_lossy().to_string();
 
     // Process the image
-    image_processor::remove_watermark(&image_path, &region, &options, &output_path_str)?;
+    image_processor::remove_watermark(&image_path, &mask_spec, &options, &output_path_str)?;
+
+    let metadata_stripped = if options.strip_metadata {
+        strip_metadata(&output_path_str)?
+    } else {
+        false
+    };
 
     // Read the result and encode as base64 for preview
     let result_bytes = fs::read(&output_path)
-        .map_err(|e| format!("Failed to read processed image: {}", e))?;
+        .map_err(|e| ProcessError::Io(format!("Failed to read processed image: {}", e)))?;
     let processed_size = result_bytes.len() as u64;
+    let blurhash = compute_blurhash(&result_bytes);
     let base64_preview = BASE64.encode(&result_bytes);
 
     // Determine MIME type
@@ -86,6 +125,8 @@ pub async fn remove_watermark(
         base64_preview: Some(format!("data:{};base64,{}", mime_type, base64_preview)),
         original_size,
         processed_size,
+        metadata_stripped,
+        blurhash,
     })
 }
 
@@ -131,7 +172,7 @@ pub async fn save_processed_image(source_path: String, destination_path: String)
 
 #[tauri::command]
 pub async fn cleanup_temp_files() -> Result<(), String> {
-    let temp_dir = get_temp_dir()?;
+    let temp_dir = get_temp_dir().map_err(|e| e.to_string())?;
     if temp_dir.exists() {
         for entry in fs::read_dir(&temp_dir).map_err(|e| format!("Failed to read temp dir: {}", e))? {
             if let Ok(entry) = entry {
@@ -142,6 +183,121 @@ pub async fn cleanup_temp_files() -> Result<(), String> {
     Ok(())
 }
 
+// ============================================
+// Batch Processing Commands
+// ============================================
+
+/// Default number of images processed concurrently when the frontend
+/// doesn't pass an explicit `max_concurrency`.
+const DEFAULT_BATCH_CONCURRENCY: usize = 4;
+
+static BATCH_CANCEL_FLAG: AtomicBool = AtomicBool::new(false);
+static BATCH_COMPLETED: AtomicU32 = AtomicU32::new(0);
+static BATCH_TOTAL: AtomicU32 = AtomicU32::new(0);
+static BATCH_FAILED: AtomicU32 = AtomicU32::new(0);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BatchProgress {
+    pub completed: u32,
+    pub total: u32,
+    pub failed: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchItemResult {
+    pub path: String,
+    pub result: Option<ProcessResult>,
+    pub error: Option<String>,
+}
+
+/// Process a folder's worth of images concurrently, bounded by a semaphore.
+///
+/// Each file is run through the same pipeline as `remove_watermark`; a
+/// single file's error doesn't abort the batch, it's recorded per-item in
+/// the returned `BatchItemResult`.
+#[tauri::command]
+pub async fn process_batch(
+    app: tauri::AppHandle,
+    image_paths: Vec<String>,
+    mask_spec: MaskSpec,
+    options: Option<RemovalOptions>,
+    max_concurrency: Option<usize>,
+) -> Result<Vec<BatchItemResult>, String> {
+    let options = options.unwrap_or_default();
+
+    BATCH_CANCEL_FLAG.store(false, Ordering::SeqCst);
+    BATCH_COMPLETED.store(0, Ordering::SeqCst);
+    BATCH_FAILED.store(0, Ordering::SeqCst);
+    BATCH_TOTAL.store(image_paths.len() as u32, Ordering::SeqCst);
+
+    let permits = max_concurrency.unwrap_or(DEFAULT_BATCH_CONCURRENCY).max(1);
+    let semaphore = Arc::new(Semaphore::new(permits));
+
+    let mut tasks = Vec::with_capacity(image_paths.len());
+    for path in image_paths {
+        let semaphore = semaphore.clone();
+        let mask_spec = mask_spec.clone();
+        let options = options.clone();
+        let app = app.clone();
+
+        tasks.push(tokio::spawn(async move {
+            let _permit = semaphore.acquire().await;
+
+            if BATCH_CANCEL_FLAG.load(Ordering::SeqCst) {
+                return BatchItemResult {
+                    path,
+                    result: None,
+                    error: Some("Batch processing cancelled".to_string()),
+                };
+            }
+
+            let outcome = remove_watermark(app, path.clone(), mask_spec, Some(options)).await;
+            BATCH_COMPLETED.fetch_add(1, Ordering::SeqCst);
+
+            match outcome {
+                Ok(result) => BatchItemResult {
+                    path,
+                    result: Some(result),
+                    error: None,
+                },
+                Err(e) => {
+                    BATCH_FAILED.fetch_add(1, Ordering::SeqCst);
+                    BatchItemResult {
+                        path,
+                        result: None,
+                        error: Some(e.to_string()),
+                    }
+                }
+            }
+        }));
+    }
+
+    let mut results = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        results.push(
+            task.await
+                .map_err(|e| format!("Batch task panicked: {}", e))?,
+        );
+    }
+
+    Ok(results)
+}
+
+#[tauri::command]
+pub async fn get_batch_progress() -> Result<BatchProgress, String> {
+    Ok(BatchProgress {
+        completed: BATCH_COMPLETED.load(Ordering::SeqCst),
+        total: BATCH_TOTAL.load(Ordering::SeqCst),
+        failed: BATCH_FAILED.load(Ordering::SeqCst),
+    })
+}
+
+#[tauri::command]
+pub async fn cancel_batch() -> Result<(), String> {
+    BATCH_CANCEL_FLAG.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
 /// Simple timestamp function to avoid adding chrono dependency
 fn chrono_lite_timestamp() -> u64 {
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -156,12 +312,20 @@ fn chrono_lite_timestamp() -> u64 {
 // ============================================
 
 #[tauri::command]
-pub async fn get_video_info(video_path: String) -> Result<VideoInfo, String> {
-    video_processor::get_video_info(&video_path)
+pub async fn get_video_info(video_path: String) -> Result<VideoInfo, ProcessError> {
+    let mut info = video_processor::get_video_info(&video_path).map_err(ProcessError::Decode)?;
+    info.blurhash = video_processor::compute_frame_blurhash(&video_path);
+    Ok(info)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FramePreview {
+    pub data_url: String,
+    pub blurhash: Option<String>,
 }
 
 #[tauri::command]
-pub async fn extract_video_frame(video_path: String, output_path: String) -> Result<String, String> {
+pub async fn extract_video_frame(video_path: String, output_path: String) -> Result<FramePreview, ProcessError> {
     // Generate output path if not provided
     let output = if output_path.is_empty() {
         let temp_dir = get_temp_dir()?;
@@ -174,28 +338,102 @@ pub async fn extract_video_frame(video_path: String, output_path: String) -> Res
         output_path
     };
 
-    video_processor::extract_first_frame(&video_path, &output)?;
+    video_processor::extract_first_frame(&video_path, &output).map_err(ProcessError::Decode)?;
 
     // Read the result and encode as base64 for preview
     let result_bytes = fs::read(&output)
-        .map_err(|e| format!("Failed to read frame image: {}", e))?;
+        .map_err(|e| ProcessError::Io(format!("Failed to read frame image: {}", e)))?;
+    let base64_data = BASE64.encode(&result_bytes);
+    let blurhash = compute_blurhash(&result_bytes);
+
+    Ok(FramePreview {
+        data_url: format!("data:image/png;base64,{}", base64_data),
+        blurhash,
+    })
+}
+
+#[tauri::command]
+pub async fn extract_video_thumbnail(
+    video_path: String,
+    frame_number: i32,
+    size: ThumbnailSize,
+    output_path: String,
+) -> Result<FramePreview, ProcessError> {
+    let output = if output_path.is_empty() {
+        let temp_dir = get_temp_dir()?;
+        let output_filename = format!("thumb_{}_{}.png",
+            Uuid::new_v4().to_string().split('-').next().unwrap_or("thumb"),
+            chrono_lite_timestamp()
+        );
+        temp_dir.join(&output_filename).to_string_lossy().to_string()
+    } else {
+        output_path
+    };
+
+    video_processor::extract_thumbnail(&video_path, frame_number, &size, &output).map_err(ProcessError::Decode)?;
+
+    let result_bytes = fs::read(&output)
+        .map_err(|e| ProcessError::Io(format!("Failed to read thumbnail image: {}", e)))?;
     let base64_data = BASE64.encode(&result_bytes);
+    let blurhash = compute_blurhash(&result_bytes);
 
-    Ok(format!("data:image/png;base64,{}", base64_data))
+    Ok(FramePreview {
+        data_url: format!("data:image/png;base64,{}", base64_data),
+        blurhash,
+    })
+}
+
+#[tauri::command]
+pub async fn generate_video_sprite_sheet(
+    video_path: String,
+    tile_count: u32,
+    size: ThumbnailSize,
+    output_path: String,
+) -> Result<SpriteSheet, ProcessError> {
+    let output = if output_path.is_empty() {
+        let temp_dir = get_temp_dir()?;
+        let output_filename = format!("sprite_{}_{}.png",
+            Uuid::new_v4().to_string().split('-').next().unwrap_or("sprite"),
+            chrono_lite_timestamp()
+        );
+        temp_dir.join(&output_filename).to_string_lossy().to_string()
+    } else {
+        output_path
+    };
+
+    Ok(video_processor::generate_sprite_sheet(&video_path, tile_count, &size, &output)?)
+}
+
+/// Compute a standalone BlurHash for an arbitrary image file, for UI
+/// components (e.g. a file browser) that want a placeholder before
+/// `remove_watermark` or `extract_video_frame` has run.
+#[tauri::command]
+pub async fn get_image_blurhash(image_path: String) -> Result<Option<String>, ProcessError> {
+    let bytes = fs::read(&image_path)
+        .map_err(|e| ProcessError::Io(format!("Failed to read image: {}", e)))?;
+    Ok(compute_blurhash(&bytes))
 }
 
 #[tauri::command]
 pub async fn process_video(
+    app: tauri::AppHandle,
     video_path: String,
-    region: WatermarkRegion,
+    mask_spec: MaskSpec,
     options: Option<RemovalOptions>,
-) -> Result<VideoProcessResult, String> {
+    output_options: Option<VideoOutputOptions>,
+) -> Result<VideoProcessResult, ProcessError> {
     let options = options.unwrap_or_default();
+    let output_options = output_options.unwrap_or_default();
 
-    // Generate unique output filename
-    let output_filename = format!("processed_{}_{}.mp4",
+    let limits = get_stored_media_limits(&app)?;
+    validate::validate_video(&video_path, &limits)?;
+
+    // Generate unique output filename, extension driven by the chosen container
+    let output_filename = format!(
+        "processed_{}_{}.{}",
         Uuid::new_v4().to_string().split('-').next().unwrap_or("vid"),
-        chrono_lite_timestamp()
+        chrono_lite_timestamp(),
+        output_options.container.to_lowercase()
     );
 
     let temp_dir = get_temp_dir()?;
@@ -203,16 +441,16 @@ pub async fn process_video(
     let output_path_str = output_path.to_string_lossy().to_string();
 
     // Process the video (this can take a long time)
-    video_processor::process_video(&video_path, &output_path_str, &region, &options)
+    video_processor::process_video(&video_path, &output_path_str, &mask_spec, &options, &output_options)
 }
 
 #[tauri::command]
-pub async fn get_video_progress() -> Result<VideoProgress, String> {
+pub async fn get_video_progress() -> Result<VideoProgress, ProcessError> {
     Ok(video_processor::get_progress())
 }
 
 #[tauri::command]
-pub async fn cancel_video_processing() -> Result<(), String> {
+pub async fn cancel_video_processing() -> Result<(), ProcessError> {
     video_processor::request_cancel();
     Ok(())
 }
@@ -223,18 +461,19 @@ pub async fn cancel_video_processing() -> Result<(), String> {
 
 const STORE_FILENAME: &str = "settings.json";
 const API_KEY_SETTING: &str = "gemini_api_key";
+const MEDIA_LIMITS_SETTING: &str = "media_limits";
 
 /// Re-encode image bytes with lossless compression
-fn reencode_lossless(bytes: &[u8], original_ext: &str) -> Result<(Vec<u8>, String), String> {
+fn reencode_lossless(bytes: &[u8], original_ext: &str) -> Result<(Vec<u8>, String), ProcessError> {
     use opencv::{imgcodecs, core::Vector};
 
     // Decode image from bytes
     let buf = Vector::<u8>::from_slice(bytes);
     let img = imgcodecs::imdecode(&buf, imgcodecs::IMREAD_COLOR)
-        .map_err(|e| format!("Failed to decode image for lossless re-encoding: {}", e))?;
+        .map_err(|e| ProcessError::Decode(format!("Failed to decode image for lossless re-encoding: {}", e)))?;
 
     if img.empty() {
-        return Err("Failed to decode image: empty result".to_string());
+        return Err(ProcessError::Decode("Failed to decode image: empty result".to_string()));
     }
 
     let (params, ext) = match original_ext.to_lowercase().as_str() {
@@ -254,30 +493,78 @@ fn reencode_lossless(bytes: &[u8], original_ext: &str) -> Result<(Vec<u8>, Strin
 
     let mut output_buf = Vector::<u8>::new();
     imgcodecs::imencode(&format!(".{}", ext), &img, &mut output_buf, &params)
-        .map_err(|e| format!("Failed to encode lossless image: {}", e))?;
+        .map_err(|e| ProcessError::Decode(format!("Failed to encode lossless image: {}", e)))?;
 
     Ok((output_buf.to_vec(), ext.to_string()))
 }
 
+/// Strip EXIF/metadata (GPS, timestamps, camera serials, ...) from a
+/// processed file in place.
+///
+/// Prefers `exiftool -all=` when it's installed, since it strips metadata
+/// without touching pixel data. Falls back to an OpenCV decode/re-encode,
+/// which drops ancillary chunks for PNG/WebP as a side effect of rewriting
+/// the container. Returns whether stripping actually occurred.
+fn strip_metadata(path: &str) -> Result<bool, ProcessError> {
+    let exiftool_result = std::process::Command::new("exiftool")
+        .args(["-all=", "-overwrite_original", path])
+        .output();
+
+    if let Ok(output) = exiftool_result {
+        if output.status.success() {
+            return Ok(true);
+        }
+    }
+
+    // exiftool missing or failed - fall back to an OpenCV re-encode, which
+    // drops ancillary metadata chunks for PNG/WebP.
+    use opencv::imgcodecs;
+
+    let extension = PathBuf::from(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("png")
+        .to_lowercase();
+
+    if extension != "png" && extension != "webp" {
+        return Ok(false);
+    }
+
+    let img = imgcodecs::imread(path, imgcodecs::IMREAD_COLOR)
+        .map_err(|e| ProcessError::Decode(format!("Failed to load image for metadata stripping: {}", e)))?;
+
+    if img.empty() {
+        return Ok(false);
+    }
+
+    imgcodecs::imwrite(path, &img, &opencv::core::Vector::<i32>::new())
+        .map_err(|e| ProcessError::Io(format!("Failed to re-encode image for metadata stripping: {}", e)))?;
+
+    Ok(true)
+}
+
 #[tauri::command]
 pub async fn remove_watermark_cloud(
     app: tauri::AppHandle,
     image_path: String,
     region: WatermarkRegion,
     options: Option<RemovalOptions>,
-) -> Result<ProcessResult, String> {
+) -> Result<ProcessResult, ProcessError> {
     let options = options.unwrap_or_default();
 
+    let limits = get_stored_media_limits(&app)?;
+    validate::validate_image(&image_path, &limits)?;
+
     // Get original file size
     let original_size = fs::metadata(&image_path)
-        .map_err(|e| format!("Failed to get original file size: {}", e))?
+        .map_err(|e| ProcessError::Io(format!("Failed to get original file size: {}", e)))?
         .len();
 
     // Get API key from store
     let api_key = get_stored_api_key(&app)?;
 
     if api_key.is_empty() {
-        return Err("Gemini API key not configured. Please set it in Settings.".to_string());
+        return Err(ProcessError::MissingApiKey);
     }
 
     let client = GeminiClient::new(api_key);
@@ -301,8 +588,6 @@ pub async fn remove_watermark_cloud(
         (processed_bytes, original_extension.to_string())
     };
 
-    let processed_size = final_bytes.len() as u64;
-
     // Generate unique output filename with final extension
     let output_filename = format!(
         "cloud_processed_{}_{}.{}",
@@ -317,10 +602,23 @@ pub async fn remove_watermark_cloud(
 
     // Save the result
     fs::write(&output_path, &final_bytes)
-        .map_err(|e| format!("Failed to save processed image: {}", e))?;
+        .map_err(|e| ProcessError::Io(format!("Failed to save processed image: {}", e)))?;
 
-    // Encode as base64 for preview
-    let base64_preview = BASE64.encode(&final_bytes);
+    let metadata_stripped = if options.strip_metadata {
+        strip_metadata(&output_path_str)?
+    } else {
+        false
+    };
+
+    // Encode as base64 for preview, re-reading the file if stripping rewrote it
+    let preview_bytes = if metadata_stripped {
+        fs::read(&output_path).map_err(|e| ProcessError::Io(format!("Failed to read processed image: {}", e)))?
+    } else {
+        final_bytes
+    };
+    let processed_size = preview_bytes.len() as u64;
+    let blurhash = compute_blurhash(&preview_bytes);
+    let base64_preview = BASE64.encode(&preview_bytes);
 
     // Determine MIME type based on final extension
     let mime_type = match final_extension.to_lowercase().as_str() {
@@ -336,6 +634,8 @@ pub async fn remove_watermark_cloud(
         base64_preview: Some(format!("data:{};base64,{}", mime_type, base64_preview)),
         original_size,
         processed_size,
+        metadata_stripped,
+        blurhash,
     })
 }
 
@@ -357,25 +657,25 @@ pub async fn set_gemini_api_key(app: tauri::AppHandle, api_key: String) -> Resul
 
 #[tauri::command]
 pub async fn get_gemini_api_key(app: tauri::AppHandle) -> Result<String, String> {
-    get_stored_api_key(&app)
+    get_stored_api_key(&app).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub async fn test_gemini_connection(app: tauri::AppHandle) -> Result<bool, String> {
+pub async fn test_gemini_connection(app: tauri::AppHandle) -> Result<bool, ProcessError> {
     let api_key = get_stored_api_key(&app)?;
 
     if api_key.is_empty() {
-        return Err("API key not configured".to_string());
+        return Err(ProcessError::MissingApiKey);
     }
 
     let client = GeminiClient::new(api_key);
-    client.test_connection().await
+    Ok(client.test_connection().await?)
 }
 
-fn get_stored_api_key(app: &tauri::AppHandle) -> Result<String, String> {
+fn get_stored_api_key(app: &tauri::AppHandle) -> Result<String, ProcessError> {
     let store = app
         .store(STORE_FILENAME)
-        .map_err(|e| format!("Failed to access store: {}", e))?;
+        .map_err(|e| ProcessError::Io(format!("Failed to access store: {}", e)))?;
 
     let api_key = store
         .get(API_KEY_SETTING)
@@ -386,13 +686,60 @@ fn get_stored_api_key(app: &tauri::AppHandle) -> Result<String, String> {
 }
 
 #[tauri::command]
-pub async fn list_gemini_models(app: tauri::AppHandle) -> Result<Vec<String>, String> {
+pub async fn list_gemini_models(app: tauri::AppHandle) -> Result<Vec<String>, ProcessError> {
     let api_key = get_stored_api_key(&app)?;
 
     if api_key.is_empty() {
-        return Err("API key not configured".to_string());
+        return Err(ProcessError::MissingApiKey);
     }
 
     let client = GeminiClient::new(api_key);
-    client.list_models().await
+    Ok(client.list_models().await?)
+}
+
+// ============================================
+// Media Limits Commands
+// ============================================
+
+/// Load the configured media limits from the settings store, falling back
+/// to defaults if nothing has been saved yet.
+///
+/// Store access failures are `Io` (a transient backend problem, worth
+/// retrying); a malformed stored JSON blob is `Decode` (the saved value
+/// itself is bad and retrying the same read won't fix it).
+fn get_stored_media_limits(app: &tauri::AppHandle) -> Result<MediaLimits, ProcessError> {
+    let store = app
+        .store(STORE_FILENAME)
+        .map_err(|e| ProcessError::Io(format!("Failed to access store: {}", e)))?;
+
+    let limits = match store.get(MEDIA_LIMITS_SETTING) {
+        Some(value) => serde_json::from_value(value)
+            .map_err(|e| ProcessError::Decode(format!("Failed to parse stored media limits: {}", e)))?,
+        None => MediaLimits::default(),
+    };
+
+    Ok(limits)
+}
+
+#[tauri::command]
+pub async fn get_media_limits(app: tauri::AppHandle) -> Result<MediaLimits, String> {
+    get_stored_media_limits(&app).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_media_limits(app: tauri::AppHandle, limits: MediaLimits) -> Result<(), String> {
+    let store = app
+        .store(STORE_FILENAME)
+        .map_err(|e| format!("Failed to access store: {}", e))?;
+
+    store.set(
+        MEDIA_LIMITS_SETTING,
+        serde_json::to_value(&limits).map_err(|e| format!("Failed to serialize media limits: {}", e))?,
+    );
+
+    store
+        .save()
+        .map_err(|e| format!("Failed to save store: {}", e))?;
+
+    Ok(())
 }
@@ -0,0 +1,88 @@
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+/// Error type returned by the processing pipeline (image + video + Gemini
+/// cloud commands) carrying a stable, frontend-matchable code instead of a
+/// bare string.
+///
+/// Serializes as `{ code, message, retryable }` so the frontend can branch
+/// on `code` (e.g. to prompt for an API key) without string-matching a
+/// human-readable message.
+#[derive(Debug)]
+pub enum ProcessError {
+    MissingApiKey,
+    InvalidInput(String),
+    UpstreamRejected { status: u16, message: String },
+    Io(String),
+    Decode(String),
+    Cancelled,
+}
+
+impl ProcessError {
+    /// Stable string identifying the error variant, safe to match on in the
+    /// frontend across releases.
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            ProcessError::MissingApiKey => "missing_api_key",
+            ProcessError::InvalidInput(_) => "invalid_input",
+            ProcessError::UpstreamRejected { .. } => "upstream_rejected",
+            ProcessError::Io(_) => "io",
+            ProcessError::Decode(_) => "decode",
+            ProcessError::Cancelled => "cancelled",
+        }
+    }
+
+    /// Whether the caller can reasonably retry the same request unchanged.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            // 5xx and 429 are transient; 4xx other than that is a client error.
+            ProcessError::UpstreamRejected { status, .. } => {
+                *status >= 500 || *status == 429
+            }
+            ProcessError::Io(_) => true,
+            ProcessError::MissingApiKey
+            | ProcessError::InvalidInput(_)
+            | ProcessError::Decode(_)
+            | ProcessError::Cancelled => false,
+        }
+    }
+}
+
+impl fmt::Display for ProcessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProcessError::MissingApiKey => {
+                write!(f, "Gemini API key not configured. Please set it in Settings.")
+            }
+            ProcessError::InvalidInput(message) => write!(f, "{}", message),
+            ProcessError::UpstreamRejected { status, message } => {
+                write!(f, "Upstream request failed ({}): {}", status, message)
+            }
+            ProcessError::Io(message) => write!(f, "{}", message),
+            ProcessError::Decode(message) => write!(f, "{}", message),
+            ProcessError::Cancelled => write!(f, "Operation cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for ProcessError {}
+
+impl Serialize for ProcessError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("ProcessError", 3)?;
+        state.serialize_field("code", self.error_code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("retryable", &self.is_retryable())?;
+        state.end()
+    }
+}
+
+impl From<std::io::Error> for ProcessError {
+    fn from(err: std::io::Error) -> Self {
+        ProcessError::Io(err.to_string())
+    }
+}
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
 
+use crate::errors::ProcessError;
 use crate::image_processor::WatermarkRegion;
 
 const GEMINI_API_URL: &str = "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.5-flash-image:generateContent";
@@ -92,10 +93,10 @@ impl GeminiClient {
         &self,
         image_path: &str,
         region: &WatermarkRegion,
-    ) -> Result<Vec<u8>, String> {
+    ) -> Result<Vec<u8>, ProcessError> {
         // Read and encode image
         let image_bytes = fs::read(image_path)
-            .map_err(|e| format!("Failed to read image: {}", e))?;
+            .map_err(|e| ProcessError::Io(format!("Failed to read image: {}", e)))?;
         let image_base64 = BASE64.encode(&image_bytes);
 
         // Determine mime type
@@ -149,61 +150,71 @@ impl GeminiClient {
             .json(&request)
             .send()
             .await
-            .map_err(|e| format!("API request failed: {}", e))?;
+            .map_err(|e| ProcessError::Io(format!("API request failed: {}", e)))?;
 
         let status = response.status();
         let response_text = response
             .text()
             .await
-            .map_err(|e| format!("Failed to read response: {}", e))?;
+            .map_err(|e| ProcessError::Io(format!("Failed to read response: {}", e)))?;
 
         if !status.is_success() {
-            return Err(format!("API returned error status {}: {}", status, response_text));
+            return Err(ProcessError::UpstreamRejected {
+                status: status.as_u16(),
+                message: response_text,
+            });
         }
 
-        let gemini_response: GeminiResponse = serde_json::from_str(&response_text)
-            .map_err(|e| format!("Failed to parse response: {}. Response: {}", e, response_text))?;
+        let gemini_response: GeminiResponse = serde_json::from_str(&response_text).map_err(|e| {
+            ProcessError::Decode(format!("Failed to parse response: {}. Response: {}", e, response_text))
+        })?;
 
         // Check for API error
         if let Some(error) = gemini_response.error {
-            return Err(format!(
-                "Gemini API error: {} (status: {})",
-                error.message,
-                error.status.unwrap_or_default()
-            ));
+            let status = error
+                .status
+                .as_deref()
+                .and_then(|s| s.parse::<u16>().ok())
+                .unwrap_or(status.as_u16());
+            return Err(ProcessError::UpstreamRejected {
+                status,
+                message: error.message,
+            });
         }
 
         // Extract image from response
         let candidates = gemini_response
             .candidates
-            .ok_or("No candidates in response")?;
+            .ok_or_else(|| ProcessError::Decode("No candidates in response".to_string()))?;
 
-        let candidate = candidates.first().ok_or("Empty candidates array")?;
+        let candidate = candidates
+            .first()
+            .ok_or_else(|| ProcessError::Decode("Empty candidates array".to_string()))?;
 
         let content = candidate
             .content
             .as_ref()
-            .ok_or("No content in candidate")?;
+            .ok_or_else(|| ProcessError::Decode("No content in candidate".to_string()))?;
 
         let parts = content
             .parts
             .as_ref()
-            .ok_or("No parts in content")?;
+            .ok_or_else(|| ProcessError::Decode("No parts in content".to_string()))?;
 
         // Find the image part
         for part in parts {
             if let Some(inline_data) = &part.inline_data {
                 let image_bytes = BASE64
                     .decode(&inline_data.data)
-                    .map_err(|e| format!("Failed to decode response image: {}", e))?;
+                    .map_err(|e| ProcessError::Decode(format!("Failed to decode response image: {}", e)))?;
                 return Ok(image_bytes);
             }
         }
 
-        Err("No image found in API response".to_string())
+        Err(ProcessError::Decode("No image found in API response".to_string()))
     }
 
-    pub async fn test_connection(&self) -> Result<bool, String> {
+    pub async fn test_connection(&self) -> Result<bool, ProcessError> {
         let url = format!(
             "https://generativelanguage.googleapis.com/v1beta/models?key={}",
             self.api_key
@@ -214,18 +225,21 @@ impl GeminiClient {
             .get(&url)
             .send()
             .await
-            .map_err(|e| format!("Connection test failed: {}", e))?;
+            .map_err(|e| ProcessError::Io(format!("Connection test failed: {}", e)))?;
 
         if response.status().is_success() {
             Ok(true)
         } else {
             let status = response.status();
             let text = response.text().await.unwrap_or_default();
-            Err(format!("API key validation failed ({}): {}", status, text))
+            Err(ProcessError::UpstreamRejected {
+                status: status.as_u16(),
+                message: text,
+            })
         }
     }
 
-    pub async fn list_models(&self) -> Result<Vec<String>, String> {
+    pub async fn list_models(&self) -> Result<Vec<String>, ProcessError> {
         let url = format!(
             "https://generativelanguage.googleapis.com/v1beta/models?key={}",
             self.api_key
@@ -236,16 +250,16 @@ impl GeminiClient {
             .get(&url)
             .send()
             .await
-            .map_err(|e| format!("Failed to list models: {}", e))?;
+            .map_err(|e| ProcessError::Io(format!("Failed to list models: {}", e)))?;
 
         let text = response
             .text()
             .await
-            .map_err(|e| format!("Failed to read response: {}", e))?;
+            .map_err(|e| ProcessError::Io(format!("Failed to read response: {}", e)))?;
 
         // Parse and extract model names
         let json: serde_json::Value = serde_json::from_str(&text)
-            .map_err(|e| format!("Failed to parse response: {}", e))?;
+            .map_err(|e| ProcessError::Decode(format!("Failed to parse response: {}", e)))?;
 
         let models = json["models"]
             .as_array()
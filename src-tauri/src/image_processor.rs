@@ -8,6 +8,8 @@ use opencv::{
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
+use crate::errors::ProcessError;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct WatermarkRegion {
     pub x: i32,
@@ -16,6 +18,82 @@ pub struct WatermarkRegion {
     pub height: i32,
 }
 
+/// Describes everything to be masked out and inpainted in a single pass:
+/// any number of axis-aligned rectangles plus, optionally, a hand-painted
+/// external mask image for irregular shapes. Both sources are ORed together
+/// into one mask, so a logo box and a hand-painted timestamp squiggle can be
+/// removed in the same inpaint call instead of separate re-encode passes.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MaskSpec {
+    #[serde(default)]
+    pub regions: Vec<WatermarkRegion>,
+    /// Path to a grayscale image the same size as the target (or resizable
+    /// to it); pixels above a mid threshold are treated as masked.
+    #[serde(default)]
+    pub mask_path: Option<String>,
+}
+
+/// Build a `CV_8UC1` mask the size of `width` x `height` by OR-ing together
+/// every rectangle in `spec.regions` and, if present, `spec.mask_path`
+/// thresholded to binary.
+pub fn build_mask(width: i32, height: i32, spec: &MaskSpec) -> Result<Mat, ProcessError> {
+    let mut mask = Mat::zeros(height, width, CV_8UC1)
+        .map_err(|e| ProcessError::Io(format!("Failed to create mask: {}", e)))?
+        .to_mat()
+        .map_err(|e| ProcessError::Io(format!("Failed to convert mask: {}", e)))?;
+
+    for region in &spec.regions {
+        if region.x < 0 || region.y < 0 || region.width <= 0 || region.height <= 0 {
+            return Err(ProcessError::InvalidInput("Invalid region dimensions".to_string()));
+        }
+        if region.x + region.width > width || region.y + region.height > height {
+            return Err(ProcessError::InvalidInput(format!(
+                "Region exceeds bounds. Target: {}x{}, Region: ({}, {}) + {}x{}",
+                width, height, region.x, region.y, region.width, region.height
+            )));
+        }
+
+        for y in region.y..(region.y + region.height) {
+            for x in region.x..(region.x + region.width) {
+                *mask.at_2d_mut::<u8>(y, x)
+                    .map_err(|e| ProcessError::Io(format!("Failed to set mask pixel: {}", e)))? = 255;
+            }
+        }
+    }
+
+    if let Some(mask_path) = &spec.mask_path {
+        let external = imgcodecs::imread(mask_path, imgcodecs::IMREAD_GRAYSCALE)
+            .map_err(|e| ProcessError::Io(format!("Failed to load mask file: {}", e)))?;
+        if external.empty() {
+            return Err(ProcessError::Decode("Failed to load mask file: empty image".to_string()));
+        }
+
+        let resized = if external.cols() != width || external.rows() != height {
+            let mut resized = Mat::default();
+            imgproc::resize(&external, &mut resized, Size::new(width, height), 0.0, 0.0, imgproc::INTER_NEAREST)
+                .map_err(|e| ProcessError::Io(format!("Failed to resize mask file: {}", e)))?;
+            resized
+        } else {
+            external
+        };
+
+        let mut thresholded = Mat::default();
+        imgproc::threshold(&resized, &mut thresholded, 127.0, 255.0, imgproc::THRESH_BINARY)
+            .map_err(|e| ProcessError::Io(format!("Failed to threshold mask file: {}", e)))?;
+
+        let mut combined = Mat::default();
+        opencv::core::bitwise_or(&mask, &thresholded, &mut combined, &opencv::core::no_array())
+            .map_err(|e| ProcessError::Io(format!("Failed to combine masks: {}", e)))?;
+        mask = combined;
+    }
+
+    if spec.regions.is_empty() && spec.mask_path.is_none() {
+        return Err(ProcessError::InvalidInput("At least one region or a mask file is required".to_string()));
+    }
+
+    Ok(mask)
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RemovalOptions {
     #[serde(default = "default_algorithm")]
@@ -26,6 +104,12 @@ pub struct RemovalOptions {
     pub inpaint_radius: f64,
     #[serde(default = "default_lossless")]
     pub lossless: bool,
+    #[serde(default = "default_strip_metadata")]
+    pub strip_metadata: bool,
+    /// Worker pool size for chunked video processing. `None` means size the
+    /// pool from `std::thread::available_parallelism()`.
+    #[serde(default)]
+    pub workers: Option<usize>,
 }
 
 fn default_algorithm() -> String {
@@ -44,6 +128,10 @@ fn default_lossless() -> bool {
     false
 }
 
+fn default_strip_metadata() -> bool {
+    false
+}
+
 impl Default for RemovalOptions {
     fn default() -> Self {
         Self {
@@ -51,52 +139,31 @@ impl Default for RemovalOptions {
             dilate_pixels: default_dilate_pixels(),
             inpaint_radius: default_inpaint_radius(),
             lossless: default_lossless(),
+            strip_metadata: default_strip_metadata(),
+            workers: None,
         }
     }
 }
 
 pub fn remove_watermark(
     image_path: &str,
-    region: &WatermarkRegion,
+    mask_spec: &MaskSpec,
     options: &RemovalOptions,
     output_path: &str,
-) -> Result<(), String> {
+) -> Result<(), ProcessError> {
     // Load the image
     let img = imgcodecs::imread(image_path, imgcodecs::IMREAD_COLOR)
-        .map_err(|e| format!("Failed to load image: {}", e))?;
+        .map_err(|e| ProcessError::Decode(format!("Failed to load image: {}", e)))?;
 
     if img.empty() {
-        return Err("Failed to load image: empty image".to_string());
+        return Err(ProcessError::Decode("Failed to load image: empty image".to_string()));
     }
 
     let img_width = img.cols();
     let img_height = img.rows();
 
-    // Validate region bounds
-    if region.x < 0 || region.y < 0 || region.width <= 0 || region.height <= 0 {
-        return Err("Invalid region dimensions".to_string());
-    }
-
-    if region.x + region.width > img_width || region.y + region.height > img_height {
-        return Err(format!(
-            "Region exceeds image bounds. Image: {}x{}, Region: ({}, {}) + {}x{}",
-            img_width, img_height, region.x, region.y, region.width, region.height
-        ));
-    }
-
-    // Create binary mask for the watermark region
-    let mut mask = Mat::zeros(img_height, img_width, CV_8UC1)
-        .map_err(|e| format!("Failed to create mask: {}", e))?
-        .to_mat()
-        .map_err(|e| format!("Failed to convert mask: {}", e))?;
-
-    // Fill the region with white (255) in the mask
-    for y in region.y..(region.y + region.height) {
-        for x in region.x..(region.x + region.width) {
-            *mask.at_2d_mut::<u8>(y, x)
-                .map_err(|e| format!("Failed to set mask pixel: {}", e))? = 255;
-        }
-    }
+    // Build a single mask covering every region plus any hand-painted mask file
+    let mut mask = build_mask(img_width, img_height, mask_spec)?;
 
     // Dilate the mask for better edge blending
     if options.dilate_pixels > 0 {
@@ -106,7 +173,7 @@ pub fn remove_watermark(
             Size::new(kernel_size, kernel_size),
             opencv::core::Point::new(-1, -1),
         )
-        .map_err(|e| format!("Failed to create kernel: {}", e))?;
+        .map_err(|e| ProcessError::Io(format!("Failed to create kernel: {}", e)))?;
 
         let mut dilated_mask = Mat::default();
         imgproc::dilate(
@@ -118,7 +185,7 @@ pub fn remove_watermark(
             BORDER_CONSTANT,
             Scalar::all(0.0),
         )
-        .map_err(|e| format!("Failed to dilate mask: {}", e))?;
+        .map_err(|e| ProcessError::Io(format!("Failed to dilate mask: {}", e)))?;
 
         mask = dilated_mask;
     }
@@ -138,7 +205,7 @@ pub fn remove_watermark(
         options.inpaint_radius,
         inpaint_method,
     )
-    .map_err(|e| format!("Inpainting failed: {}", e))?;
+    .map_err(|e| ProcessError::Io(format!("Inpainting failed: {}", e)))?;
 
     // Save the result
     let output_path_obj = Path::new(output_path);
@@ -189,11 +256,65 @@ pub fn remove_watermark(
     };
 
     imgcodecs::imwrite(&final_output_path, &result, &params)
-        .map_err(|e| format!("Failed to save result: {}", e))?;
+        .map_err(|e| ProcessError::Io(format!("Failed to save result: {}", e)))?;
 
     Ok(())
 }
 
+/// Sizing mode for a generated thumbnail.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ThumbnailSize {
+    /// Fit the longest side to `max_dimension`, preserving aspect ratio.
+    Scale { max_dimension: i32 },
+    /// Resize to an exact `width` x `height`, ignoring aspect ratio.
+    Exact { width: i32, height: i32 },
+}
+
+/// Resize `mat` to the requested thumbnail size using area interpolation,
+/// which is the right choice for downscaling (the common case for previews).
+pub fn resize_for_thumbnail(mat: &Mat, size: &ThumbnailSize) -> Result<Mat, ProcessError> {
+    let src_width = mat.cols();
+    let src_height = mat.rows();
+    if src_width <= 0 || src_height <= 0 {
+        return Err(ProcessError::InvalidInput("Cannot thumbnail an empty image".to_string()));
+    }
+
+    let (target_width, target_height) = match *size {
+        ThumbnailSize::Scale { max_dimension } => {
+            if max_dimension <= 0 {
+                return Err(ProcessError::InvalidInput("max_dimension must be positive".to_string()));
+            }
+            if src_width >= src_height {
+                let height = (max_dimension as f64 * src_height as f64 / src_width as f64).round() as i32;
+                (max_dimension, height.max(1))
+            } else {
+                let width = (max_dimension as f64 * src_width as f64 / src_height as f64).round() as i32;
+                (width.max(1), max_dimension)
+            }
+        }
+        ThumbnailSize::Exact { width, height } => {
+            if width <= 0 || height <= 0 {
+                return Err(ProcessError::InvalidInput("width and height must be positive".to_string()));
+            }
+            (width, height)
+        }
+    };
+
+    let mut thumbnail = Mat::default();
+    imgproc::resize(
+        mat,
+        &mut thumbnail,
+        Size::new(target_width, target_height),
+        0.0,
+        0.0,
+        imgproc::INTER_AREA,
+    )
+    .map_err(|e| ProcessError::Io(format!("Failed to resize thumbnail: {}", e)))?;
+
+    Ok(thumbnail)
+}
+
 pub fn get_image_dimensions(image_path: &str) -> Result<(i32, i32), String> {
     let img = imgcodecs::imread(image_path, imgcodecs::IMREAD_COLOR)
         .map_err(|e| format!("Failed to load image: {}", e))?;
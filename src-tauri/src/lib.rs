@@ -1,18 +1,28 @@
+mod blurhash;
 mod commands;
+mod errors;
 mod gemini_client;
 mod image_processor;
+mod validate;
 mod video_processor;
 
 use commands::{
     // Image commands
     cleanup_temp_files,
+    get_image_blurhash,
     get_image_info,
     load_image_base64,
     remove_watermark,
     save_processed_image,
+    // Batch commands
+    cancel_batch,
+    get_batch_progress,
+    process_batch,
     // Video commands
     cancel_video_processing,
     extract_video_frame,
+    extract_video_thumbnail,
+    generate_video_sprite_sheet,
     get_video_info,
     get_video_progress,
     process_video,
@@ -22,6 +32,9 @@ use commands::{
     remove_watermark_cloud,
     set_gemini_api_key,
     test_gemini_connection,
+    // Media limits
+    get_media_limits,
+    set_media_limits,
 };
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -38,9 +51,16 @@ pub fn run() {
             load_image_base64,
             save_processed_image,
             cleanup_temp_files,
+            get_image_blurhash,
+            // Batch commands
+            process_batch,
+            get_batch_progress,
+            cancel_batch,
             // Video commands
             get_video_info,
             extract_video_frame,
+            extract_video_thumbnail,
+            generate_video_sprite_sheet,
             process_video,
             get_video_progress,
             cancel_video_processing,
@@ -50,6 +70,9 @@ pub fn run() {
             get_gemini_api_key,
             test_gemini_connection,
             list_gemini_models,
+            // Media limits
+            get_media_limits,
+            set_media_limits,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::errors::ProcessError;
+use crate::image_processor;
+use crate::video_processor;
+
+/// Hard caps on input media, checked before a command starts processing.
+///
+/// Mirrors pict-rs's media-limit checks: reject oversized/unsupported input
+/// up front rather than letting `image_processor`/`video_processor` run out
+/// of memory or hang on a multi-hour video.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MediaLimits {
+    #[serde(default = "default_max_width")]
+    pub max_width: i32,
+    #[serde(default = "default_max_height")]
+    pub max_height: i32,
+    #[serde(default = "default_max_file_bytes")]
+    pub max_file_bytes: u64,
+    #[serde(default = "default_max_frames")]
+    pub max_frames: i32,
+    #[serde(default = "default_allowed_formats")]
+    pub allowed_formats: Vec<String>,
+}
+
+fn default_max_width() -> i32 {
+    10_000
+}
+
+fn default_max_height() -> i32 {
+    10_000
+}
+
+fn default_max_file_bytes() -> u64 {
+    500 * 1024 * 1024 // 500 MB
+}
+
+fn default_max_frames() -> i32 {
+    108_000 // ~1 hour at 30fps
+}
+
+fn default_allowed_formats() -> Vec<String> {
+    ["png", "jpg", "jpeg", "webp", "gif", "mp4", "mov", "avi", "mkv", "webm"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+impl Default for MediaLimits {
+    fn default() -> Self {
+        Self {
+            max_width: default_max_width(),
+            max_height: default_max_height(),
+            max_file_bytes: default_max_file_bytes(),
+            max_frames: default_max_frames(),
+            allowed_formats: default_allowed_formats(),
+        }
+    }
+}
+
+fn extension_of(path: &str) -> String {
+    Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase()
+}
+
+fn check_format(path: &str, limits: &MediaLimits) -> Result<(), ProcessError> {
+    let extension = extension_of(path);
+    if !limits.allowed_formats.iter().any(|f| f == &extension) {
+        return Err(ProcessError::InvalidInput(format!(
+            "File format '{}' is not in the allowed list: {}",
+            extension,
+            limits.allowed_formats.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+fn check_file_bytes(path: &str, limits: &MediaLimits) -> Result<(), ProcessError> {
+    let size = std::fs::metadata(path)
+        .map_err(|e| ProcessError::Io(format!("Failed to read file metadata: {}", e)))?
+        .len();
+    if size > limits.max_file_bytes {
+        return Err(ProcessError::InvalidInput(format!(
+            "File size {} bytes exceeds the {} byte limit",
+            size, limits.max_file_bytes
+        )));
+    }
+    Ok(())
+}
+
+/// Validate an image against the configured limits before it is handed to
+/// `image_processor`.
+pub fn validate_image(path: &str, limits: &MediaLimits) -> Result<(), ProcessError> {
+    check_format(path, limits)?;
+    check_file_bytes(path, limits)?;
+
+    let (width, height) = image_processor::get_image_dimensions(path)
+        .map_err(ProcessError::Decode)?;
+    if width > limits.max_width || height > limits.max_height {
+        return Err(ProcessError::InvalidInput(format!(
+            "image exceeds {}x{}: got {}x{}",
+            limits.max_width, limits.max_height, width, height
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validate a video against the configured limits before it is handed to
+/// `video_processor`.
+pub fn validate_video(path: &str, limits: &MediaLimits) -> Result<(), ProcessError> {
+    check_format(path, limits)?;
+    check_file_bytes(path, limits)?;
+
+    let info = video_processor::get_video_info(path).map_err(ProcessError::Decode)?;
+    if info.width > limits.max_width || info.height > limits.max_height {
+        return Err(ProcessError::InvalidInput(format!(
+            "video exceeds {}x{}: got {}x{}",
+            limits.max_width, limits.max_height, info.width, info.height
+        )));
+    }
+    if info.frame_count > limits.max_frames {
+        return Err(ProcessError::InvalidInput(format!(
+            "video has too many frames: {} exceeds the limit of {}",
+            info.frame_count, limits.max_frames
+        )));
+    }
+
+    Ok(())
+}
@@ -1,26 +1,41 @@
 use opencv::{
-    core::{Mat, MatTraitConst, Size, CV_8UC1, BORDER_CONSTANT, Scalar},
+    core::{Mat, MatTraitConst, Size, BORDER_CONSTANT, Scalar},
     imgcodecs,
     imgproc,
     photo,
     prelude::*,
-    videoio::{self, VideoCapture, VideoWriter, CAP_ANY},
+    videoio::{self, VideoCapture, CAP_ANY},
 };
 use serde::{Deserialize, Serialize};
+use std::io::Write;
 use std::process::Command;
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
 
-use crate::image_processor::{RemovalOptions, WatermarkRegion};
+use crate::errors::ProcessError;
+use crate::image_processor::{self, MaskSpec, RemovalOptions, ThumbnailSize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct VideoInfo {
     pub width: i32,
     pub height: i32,
     pub fps: f64,
+    /// Exact frame rate as a rational `fps_num / fps_den`, straight from the
+    /// container's `r_frame_rate`/`avg_frame_rate` when probed via ffprobe.
+    /// Falls back to `(fps.round() as i32, 1)` on the OpenCV-only path.
+    pub fps_num: i32,
+    pub fps_den: i32,
     pub frame_count: i32,
     pub duration_secs: f64,
     pub codec: String,
+    pub pixel_format: String,
+    pub container_format: String,
+    pub has_audio: bool,
     pub path: String,
+    /// BlurHash of the first frame, for an instant low-res placeholder
+    /// while the real preview loads. `None` if it couldn't be computed.
+    pub blurhash: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -36,6 +51,166 @@ pub struct VideoProcessResult {
     pub output_path: String,
     pub frames_processed: u32,
     pub duration_secs: f64,
+    /// BlurHash of the processed output's first frame, for an instant
+    /// low-res placeholder in the UI.
+    pub blurhash: Option<String>,
+}
+
+/// Container/codec choice for `process_video`'s output file.
+///
+/// Defaults to mp4/h264/aac so existing callers that don't pass this are
+/// unaffected.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VideoOutputOptions {
+    #[serde(default = "default_container")]
+    pub container: String,
+    /// Either a friendly family name (`h264`, `h265`, `vp9`, `vp8`) or a raw
+    /// ffmpeg encoder name (`libx264`, `libsvtav1`, `h264_vaapi`,
+    /// `h264_nvenc`, ...) passed straight through to `-c:v` for hardware
+    /// acceleration or codecs the friendly names don't cover.
+    #[serde(default = "default_video_codec")]
+    pub video_codec: String,
+    #[serde(default = "default_audio_codec")]
+    pub audio_codec: String,
+    #[serde(default = "default_crf")]
+    pub crf: i32,
+    /// ffmpeg encoder preset (e.g. `ultrafast`..`veryslow` for x264/x265).
+    /// Passed as `-preset` only to software encoders (names starting with
+    /// `lib`); hardware encoders such as `h264_nvenc` never receive it.
+    #[serde(default = "default_preset")]
+    pub preset: String,
+    /// Target bitrate (e.g. `"4M"`), passed as `-b:v`. Takes priority over
+    /// `crf` when set - mainly useful for hardware encoders that don't
+    /// support quality-based rate control.
+    #[serde(default)]
+    pub bitrate: Option<String>,
+}
+
+fn default_container() -> String {
+    "mp4".to_string()
+}
+
+fn default_video_codec() -> String {
+    "h264".to_string()
+}
+
+fn default_audio_codec() -> String {
+    "aac".to_string()
+}
+
+fn default_crf() -> i32 {
+    23
+}
+
+fn default_preset() -> String {
+    "medium".to_string()
+}
+
+impl Default for VideoOutputOptions {
+    fn default() -> Self {
+        Self {
+            container: default_container(),
+            video_codec: default_video_codec(),
+            audio_codec: default_audio_codec(),
+            crf: default_crf(),
+            preset: default_preset(),
+            bitrate: None,
+        }
+    }
+}
+
+/// Resolved (file extension, ffmpeg `-c:v` encoder name, ffmpeg `-c:a`
+/// encoder name) for a container/codec/audio-codec triple. `audio_encoder`
+/// is empty when audio was explicitly disabled (`audio_codec: "none"`).
+struct ResolvedOutputFormat {
+    extension: &'static str,
+    encoder: String,
+    audio_encoder: String,
+}
+
+/// Validate the container/codec combination and resolve it to a concrete
+/// ffmpeg encoder name, rejecting incompatible pairs (e.g. webm + h264).
+///
+/// `gif` is not a supported output container: ffmpeg's `gif` encoder has no
+/// `-crf`/`-preset` options (see `spawn_frame_encoder`) and needs a separate
+/// palette-generation pass for non-garbled output, neither of which this
+/// pipeline does.
+///
+/// A `video_codec` that looks like a raw ffmpeg encoder name already (starts
+/// with `lib`, or names a hardware variant like `h264_nvenc`/`h264_vaapi`)
+/// is passed straight through unmodified, trusting the caller's ffmpeg build
+/// supports it.
+fn resolve_output_format(options: &VideoOutputOptions) -> Result<ResolvedOutputFormat, ProcessError> {
+    let container = options.container.to_lowercase();
+    let video_codec = options.video_codec.to_lowercase();
+
+    let extension: &'static str = match container.as_str() {
+        "mp4" => "mp4",
+        "webm" => "webm",
+        _ => {
+            return Err(ProcessError::InvalidInput(format!(
+                "Unsupported container: {}",
+                options.container
+            )))
+        }
+    };
+
+    let is_raw_encoder_name = video_codec.starts_with("lib") || video_codec.contains('_');
+    let encoder = if is_raw_encoder_name {
+        video_codec
+    } else {
+        match (container.as_str(), video_codec.as_str()) {
+            ("mp4", "h264") => "libx264",
+            ("mp4", "h265") => "libx265",
+            ("webm", "vp9") => "libvpx-vp9",
+            ("webm", "vp8") => "libvpx",
+            _ => {
+                return Err(ProcessError::InvalidInput(format!(
+                    "Unsupported container/codec combination: {}/{}",
+                    options.container, options.video_codec
+                )))
+            }
+        }
+        .to_string()
+    };
+
+    let audio_encoder = resolve_audio_encoder(&container, &options.audio_codec)?;
+
+    Ok(ResolvedOutputFormat { extension, encoder, audio_encoder })
+}
+
+/// Resolve the audio codec the muxer will actually accept for `container`.
+///
+/// `audio_codec: "none"` opts out explicitly, resolving to an empty encoder
+/// (the caller skips the audio pipeline entirely). Otherwise the friendly
+/// audio codec names are forced to whatever `container`'s muxer supports -
+/// e.g. `webm` never accepts `aac`, so any non-Vorbis choice there becomes
+/// Opus - since silently muxing an incompatible pair fails late, deep into
+/// `merge_audio`, instead of up front.
+fn resolve_audio_encoder(container: &str, audio_codec: &str) -> Result<String, ProcessError> {
+    let audio_codec = audio_codec.to_lowercase();
+    if audio_codec == "none" {
+        return Ok(String::new());
+    }
+
+    let encoder = match container {
+        "webm" => match audio_codec.as_str() {
+            "vorbis" | "libvorbis" => "libvorbis",
+            _ => "libopus",
+        },
+        "mp4" => match audio_codec.as_str() {
+            "opus" | "libopus" => "libopus",
+            _ => "aac",
+        },
+        _ => {
+            return Err(ProcessError::InvalidInput(format!(
+                "Unsupported container for audio: {}",
+                container
+            )))
+        }
+    };
+
+    Ok(encoder.to_string())
 }
 
 /// Global cancellation flag for video processing
@@ -43,14 +218,33 @@ static CANCEL_FLAG: AtomicBool = AtomicBool::new(false);
 static CURRENT_FRAME: AtomicU32 = AtomicU32::new(0);
 static TOTAL_FRAMES: AtomicU32 = AtomicU32::new(0);
 
+/// Smoothing factor for the frames-per-second EMA. Low enough that
+/// early-frame variance and codec warmup don't dominate the estimate.
+const THROUGHPUT_EMA_ALPHA: f64 = 0.1;
+
+/// Frames-per-second throughput tracked as an exponential moving average,
+/// updated once per completed frame across all segment worker threads.
+struct ThroughputState {
+    ema_fps: f64,
+    last_instant: Option<Instant>,
+}
+
+static THROUGHPUT: Mutex<ThroughputState> = Mutex::new(ThroughputState {
+    ema_fps: 0.0,
+    last_instant: None,
+});
+
 /// Request cancellation of current video processing
 pub fn request_cancel() {
     CANCEL_FLAG.store(true, Ordering::SeqCst);
 }
 
-/// Reset cancellation flag
+/// Reset cancellation flag and throughput tracking for a fresh run
 fn reset_cancel() {
     CANCEL_FLAG.store(false, Ordering::SeqCst);
+    let mut throughput = THROUGHPUT.lock().unwrap();
+    throughput.ema_fps = 0.0;
+    throughput.last_instant = None;
 }
 
 /// Check if cancellation was requested
@@ -58,6 +252,27 @@ fn is_cancelled() -> bool {
     CANCEL_FLAG.load(Ordering::SeqCst)
 }
 
+/// Record that a frame finished processing: advances the frame counter and
+/// folds the instantaneous rate into the throughput EMA.
+fn advance_frame() {
+    CURRENT_FRAME.fetch_add(1, Ordering::SeqCst);
+
+    let now = Instant::now();
+    let mut throughput = THROUGHPUT.lock().unwrap();
+    if let Some(last) = throughput.last_instant {
+        let dt = now.duration_since(last).as_secs_f64();
+        if dt > 0.0 {
+            let rate = 1.0 / dt;
+            throughput.ema_fps = if throughput.ema_fps == 0.0 {
+                rate
+            } else {
+                THROUGHPUT_EMA_ALPHA * rate + (1.0 - THROUGHPUT_EMA_ALPHA) * throughput.ema_fps
+            };
+        }
+    }
+    throughput.last_instant = Some(now);
+}
+
 /// Get current processing progress
 pub fn get_progress() -> VideoProgress {
     let current = CURRENT_FRAME.load(Ordering::SeqCst);
@@ -67,16 +282,171 @@ pub fn get_progress() -> VideoProgress {
     } else {
         0.0
     };
+    let ema_fps = THROUGHPUT.lock().unwrap().ema_fps;
+    let estimated_remaining_secs = if ema_fps > 0.0 && total > current {
+        Some((total - current) as f64 / ema_fps)
+    } else {
+        None
+    };
     VideoProgress {
         current_frame: current,
         total_frames: total,
         percent,
-        estimated_remaining_secs: None,
+        estimated_remaining_secs,
     }
 }
 
-/// Get video information
+/// Number of DCT basis functions used for the first-frame preview BlurHash.
+const PREVIEW_BLURHASH_COMPONENTS_X: u32 = 4;
+const PREVIEW_BLURHASH_COMPONENTS_Y: u32 = 3;
+
+/// Compute a BlurHash of a video's first frame, swallowing failures since
+/// the hash is a nice-to-have preview rather than something that should
+/// fail the whole probe/process call.
+///
+/// Not called from `get_video_info` itself: both `validate_video` and
+/// `process_video` probe the input purely for its metadata and never read
+/// `VideoInfo::blurhash`, so computing it there decoded the first frame
+/// twice per job for no reason. Callers that actually need a preview (the
+/// `get_video_info` Tauri command) compute it once, on demand.
+pub(crate) fn compute_frame_blurhash(video_path: &str) -> Option<String> {
+    let frame = extract_frame(video_path, 0).ok()?;
+    crate::blurhash::encode(&frame, PREVIEW_BLURHASH_COMPONENTS_X, PREVIEW_BLURHASH_COMPONENTS_Y).ok()
+}
+
+/// Get video information. Prefers shelling out to `ffprobe` for richer,
+/// more reliable fields (exact rational frame rate, real codec/pixel format,
+/// whether an audio stream exists) and falls back to the OpenCV-only probe
+/// if `ffprobe` isn't installed or fails to parse the file.
+///
+/// Leaves `VideoInfo::blurhash` unset - this probe is also used by
+/// `validate_video` and `process_video`, which only need the dimensions/
+/// frame count/codec fields, so it doesn't pay for a first-frame decode
+/// that would go unused. Use `compute_frame_blurhash` where a preview hash
+/// is actually wanted.
 pub fn get_video_info(video_path: &str) -> Result<VideoInfo, String> {
+    match probe_with_ffprobe(video_path) {
+        Ok(info) => Ok(info),
+        Err(_) => get_video_info_opencv(video_path),
+    }
+}
+
+/// ffprobe JSON shape for the subset of `-show_streams -show_format` we use.
+#[derive(Debug, Deserialize)]
+struct FfprobeOutput {
+    streams: Vec<FfprobeStream>,
+    format: FfprobeFormat,
+}
+
+#[derive(Debug, Deserialize)]
+struct FfprobeStream {
+    codec_type: String,
+    codec_name: Option<String>,
+    pix_fmt: Option<String>,
+    width: Option<i32>,
+    height: Option<i32>,
+    #[serde(default)]
+    avg_frame_rate: String,
+    #[serde(default)]
+    r_frame_rate: String,
+    nb_frames: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FfprobeFormat {
+    format_name: String,
+    duration: Option<String>,
+}
+
+/// Parse a `"num/den"` rational string (ffprobe's frame-rate format) into
+/// its integer parts, rejecting degenerate `0/0`/unparseable values.
+fn parse_rational(value: &str) -> Option<(i32, i32)> {
+    let (num, den) = value.split_once('/')?;
+    let num: i32 = num.trim().parse().ok()?;
+    let den: i32 = den.trim().parse().ok()?;
+    if den == 0 {
+        None
+    } else {
+        Some((num, den))
+    }
+}
+
+fn probe_with_ffprobe(video_path: &str) -> Result<VideoInfo, String> {
+    let output = Command::new("ffprobe")
+        .args([
+            "-v", "quiet",
+            "-print_format", "json",
+            "-show_streams",
+            "-show_format",
+        ])
+        .arg(video_path)
+        .output()
+        .map_err(|e| format!("Failed to run ffprobe: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "ffprobe failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    let parsed: FfprobeOutput = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse ffprobe output: {}", e))?;
+
+    let video_stream = parsed
+        .streams
+        .iter()
+        .find(|s| s.codec_type == "video")
+        .ok_or_else(|| "No video stream found".to_string())?;
+
+    let has_audio = parsed.streams.iter().any(|s| s.codec_type == "audio");
+
+    let (fps_num, fps_den) = parse_rational(&video_stream.avg_frame_rate)
+        .or_else(|| parse_rational(&video_stream.r_frame_rate))
+        .ok_or_else(|| "Could not determine frame rate".to_string())?;
+    let fps = if fps_den != 0 { fps_num as f64 / fps_den as f64 } else { 0.0 };
+
+    let width = video_stream
+        .width
+        .ok_or_else(|| "Video stream missing width".to_string())?;
+    let height = video_stream
+        .height
+        .ok_or_else(|| "Video stream missing height".to_string())?;
+
+    let duration_secs: f64 = parsed
+        .format
+        .duration
+        .as_deref()
+        .and_then(|d| d.parse().ok())
+        .unwrap_or(0.0);
+
+    let frame_count = video_stream
+        .nb_frames
+        .as_deref()
+        .and_then(|n| n.parse::<i32>().ok())
+        .unwrap_or_else(|| (duration_secs * fps).round() as i32);
+
+    Ok(VideoInfo {
+        width,
+        height,
+        fps,
+        fps_num,
+        fps_den,
+        frame_count,
+        duration_secs,
+        codec: video_stream.codec_name.clone().unwrap_or_else(|| "unknown".to_string()),
+        pixel_format: video_stream.pix_fmt.clone().unwrap_or_else(|| "unknown".to_string()),
+        container_format: parsed.format.format_name.clone(),
+        has_audio,
+        path: video_path.to_string(),
+        blurhash: None,
+    })
+}
+
+/// OpenCV `CAP_PROP_*`-based fallback probe, used when `ffprobe` is
+/// unavailable. Frame counts and codec strings from this path are
+/// best-effort only - OpenCV frequently misreports them.
+fn get_video_info_opencv(video_path: &str) -> Result<VideoInfo, String> {
     let mut cap = VideoCapture::from_file(video_path, CAP_ANY)
         .map_err(|e| format!("Failed to open video: {}", e))?;
 
@@ -103,10 +473,16 @@ pub fn get_video_info(video_path: &str) -> Result<VideoInfo, String> {
         width,
         height,
         fps,
+        fps_num: fps.round() as i32,
+        fps_den: 1,
         frame_count,
         duration_secs,
         codec,
+        pixel_format: "unknown".to_string(),
+        container_format: "unknown".to_string(),
+        has_audio: true,
         path: video_path.to_string(),
+        blurhash: None,
     })
 }
 
@@ -145,106 +521,441 @@ pub fn extract_first_frame(video_path: &str, output_path: &str) -> Result<(), St
     Ok(())
 }
 
+/// Extract a single frame and save it resized to `size`, so UI preview
+/// grids don't have to ship full-resolution images.
+pub fn extract_thumbnail(
+    video_path: &str,
+    frame_number: i32,
+    size: &ThumbnailSize,
+    output_path: &str,
+) -> Result<(), ProcessError> {
+    let frame = extract_frame(video_path, frame_number).map_err(ProcessError::Io)?;
+    let thumbnail = image_processor::resize_for_thumbnail(&frame, size)?;
+
+    let params = opencv::core::Vector::<i32>::from_slice(&[imgcodecs::IMWRITE_PNG_COMPRESSION, 6]);
+    imgcodecs::imwrite(output_path, &thumbnail, &params)
+        .map_err(|e| ProcessError::Io(format!("Failed to save thumbnail: {}", e)))?;
+
+    Ok(())
+}
+
+/// Metadata for a generated sprite sheet: evenly spaced thumbnails laid out
+/// in a single horizontal strip so a timeline scrubber can slice it by tile
+/// index (`tile_index * tile_width` into the strip).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SpriteSheet {
+    pub output_path: String,
+    pub tile_width: i32,
+    pub tile_height: i32,
+    pub tile_count: u32,
+}
+
+/// Generate a sprite sheet of `tile_count` thumbnails sampled evenly across
+/// the video, laid out left-to-right in a single strip.
+pub fn generate_sprite_sheet(
+    video_path: &str,
+    tile_count: u32,
+    size: &ThumbnailSize,
+    output_path: &str,
+) -> Result<SpriteSheet, ProcessError> {
+    if tile_count == 0 {
+        return Err(ProcessError::InvalidInput("tile_count must be positive".to_string()));
+    }
+
+    let info = get_video_info(video_path).map_err(ProcessError::Decode)?;
+    if info.frame_count <= 0 {
+        return Err(ProcessError::InvalidInput("Video has no frames".to_string()));
+    }
+
+    let step = (info.frame_count as f64 / tile_count as f64).max(1.0);
+    let mut tiles = opencv::core::Vector::<Mat>::new();
+    let mut tile_width = 0;
+    let mut tile_height = 0;
+
+    for i in 0..tile_count {
+        let frame_number = ((i as f64 * step) as i32).min(info.frame_count - 1);
+        let frame = extract_frame(video_path, frame_number).map_err(ProcessError::Io)?;
+        let thumbnail = image_processor::resize_for_thumbnail(&frame, size)?;
+        tile_width = thumbnail.cols();
+        tile_height = thumbnail.rows();
+        tiles.push(thumbnail);
+    }
+
+    let mut sheet = Mat::default();
+    opencv::core::hconcat(&tiles, &mut sheet)
+        .map_err(|e| ProcessError::Io(format!("Failed to assemble sprite sheet: {}", e)))?;
+
+    let params = opencv::core::Vector::<i32>::from_slice(&[imgcodecs::IMWRITE_PNG_COMPRESSION, 6]);
+    imgcodecs::imwrite(output_path, &sheet, &params)
+        .map_err(|e| ProcessError::Io(format!("Failed to save sprite sheet: {}", e)))?;
+
+    Ok(SpriteSheet {
+        output_path: output_path.to_string(),
+        tile_width,
+        tile_height,
+        tile_count,
+    })
+}
+
 /// Process video by removing watermark from each frame
 pub fn process_video(
     input_path: &str,
     output_path: &str,
-    region: &WatermarkRegion,
+    mask_spec: &MaskSpec,
     options: &RemovalOptions,
-) -> Result<VideoProcessResult, String> {
+    output_options: &VideoOutputOptions,
+) -> Result<VideoProcessResult, ProcessError> {
     reset_cancel();
     let start_time = std::time::Instant::now();
 
+    let output_format = resolve_output_format(output_options)?;
+
     // Get video info
-    let info = get_video_info(input_path)?;
+    let info = get_video_info(input_path).map_err(ProcessError::Decode)?;
     TOTAL_FRAMES.store(info.frame_count as u32, Ordering::SeqCst);
     CURRENT_FRAME.store(0, Ordering::SeqCst);
 
-    // Validate region bounds
-    if region.x < 0 || region.y < 0 || region.width <= 0 || region.height <= 0 {
-        return Err("Invalid region dimensions".to_string());
-    }
-
-    if region.x + region.width > info.width || region.y + region.height > info.height {
-        return Err(format!(
-            "Region exceeds video bounds. Video: {}x{}, Region: ({}, {}) + {}x{}",
-            info.width, info.height, region.x, region.y, region.width, region.height
-        ));
-    }
+    // Fail fast on bad regions/mask file before spawning any workers
+    image_processor::build_mask(info.width, info.height, mask_spec)?;
 
-    // Extract audio from source video (if exists)
+    // Extract audio from source video (if exists and the output format wants it)
     let temp_dir = std::env::temp_dir().join("watermark-remover");
-    std::fs::create_dir_all(&temp_dir)
-        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+    std::fs::create_dir_all(&temp_dir)?;
 
-    let audio_path = temp_dir.join("audio_temp.aac");
-    let video_without_audio_path = temp_dir.join("video_no_audio.mp4");
-    let has_audio = extract_audio(input_path, audio_path.to_string_lossy().as_ref());
+    let wants_audio = info.has_audio && !output_format.audio_encoder.is_empty();
+    let audio_path = temp_dir.join("audio_temp").with_extension(audio_file_extension(&output_format.audio_encoder));
+    let video_without_audio_path = temp_dir
+        .join("video_no_audio")
+        .with_extension(output_format.extension);
+    let has_audio = wants_audio
+        && extract_audio(
+            input_path,
+            audio_path.to_string_lossy().as_ref(),
+            &output_format.audio_encoder,
+        );
 
-    // Open input video
-    let mut cap = VideoCapture::from_file(input_path, CAP_ANY)
-        .map_err(|e| format!("Failed to open video: {}", e))?;
+    // Size the worker pool: explicit `options.workers` wins, otherwise size
+    // from the machine's parallelism.
+    let workers = options
+        .workers
+        .unwrap_or_else(|| std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1))
+        .max(1);
 
-    if !cap.is_opened().map_err(|e| format!("Failed to check video: {}", e))? {
-        return Err("Failed to open video file".to_string());
-    }
+    // `CAP_PROP_POS_FRAMES` only seeks frame-accurately on all-intra input;
+    // on long-GOP, inter-coded video (h264, h265, vp9 - everything this app
+    // actually ingests/outputs) it lands on the nearest keyframe, so a
+    // worker seeking to an arbitrary segment boundary can start a few frames
+    // off and drop or duplicate frames at the join. Parallel segment
+    // dispatch - and the multicore speedup it buys - is therefore only safe
+    // when every frame is independently decodable; everything else runs as
+    // a single sequential segment, matching the pre-parallel baseline.
+    let workers = if supports_parallel_seek(&info.codec) { workers } else { 1 };
 
-    // Determine output codec
-    let fourcc = VideoWriter::fourcc('a', 'v', 'c', '1')
-        .map_err(|e| format!("Failed to create fourcc: {}", e))?;
+    // Split the input into segments, snapped to scene-change boundaries
+    // where possible, so no watermark-continuity artifact straddles a cut.
+    // Scene analysis decodes the whole video a second time, so it's only
+    // worth the cost when there's more than one segment to snap.
+    let boundaries = if workers <= 1 {
+        fixed_window_boundaries(info.frame_count, workers)
+    } else {
+        match compute_scene_scores(input_path, info.frame_count) {
+            Ok(scores) if !scores.is_empty() => {
+                compute_segment_boundaries(&scores, info.frame_count, workers)
+            }
+            _ => fixed_window_boundaries(info.frame_count, workers),
+        }
+    };
 
-    // Create video writer
     let output_for_writer = if has_audio {
         video_without_audio_path.to_string_lossy().to_string()
     } else {
         output_path.to_string()
     };
 
-    let mut writer = VideoWriter::new(
-        &output_for_writer,
-        fourcc,
-        info.fps,
-        Size::new(info.width, info.height),
-        true,
-    )
-    .map_err(|e| format!("Failed to create video writer: {}", e))?;
+    let segment_paths: Vec<std::path::PathBuf> = (0..boundaries.len())
+        .map(|i| {
+            temp_dir.join(format!(
+                "segment_{}_{}.{}",
+                std::process::id(),
+                i,
+                output_format.extension
+            ))
+        })
+        .collect();
+
+    // Each worker opens its own VideoCapture, seeks to its start frame, and
+    // inpaints its range into its own temp file.
+    let segment_results: Vec<Result<SegmentOutcome, ProcessError>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = boundaries
+            .iter()
+            .zip(segment_paths.iter())
+            .map(|(&(start, end), path)| {
+                scope.spawn(move || {
+                    process_segment(
+                        input_path,
+                        path,
+                        start,
+                        end,
+                        info.fps_num,
+                        info.fps_den,
+                        info.width,
+                        info.height,
+                        &output_format.encoder,
+                        output_options,
+                        mask_spec,
+                        options,
+                    )
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|_| Err(ProcessError::Io("Segment worker panicked".to_string())))
+            })
+            .collect()
+    });
+
+    let mut processed_frames = 0u32;
+    let mut first_error = None;
+    for outcome in segment_results {
+        match outcome {
+            Ok(segment) => processed_frames += segment.frames_processed,
+            Err(e) if first_error.is_none() => first_error = Some(e),
+            Err(_) => {}
+        }
+    }
+
+    if let Some(e) = first_error {
+        for path in &segment_paths {
+            let _ = std::fs::remove_file(path);
+        }
+        if has_audio {
+            let _ = std::fs::remove_file(&audio_path);
+        }
+        return Err(e);
+    }
+
+    // Concatenate segments (ffmpeg concat demuxer, stream copy - no re-encode)
+    concat_segments(&segment_paths, &output_for_writer).map_err(ProcessError::Io)?;
+    for path in &segment_paths {
+        let _ = std::fs::remove_file(path);
+    }
+
+    // Merge audio back if it existed
+    if has_audio {
+        merge_audio(
+            video_without_audio_path.to_string_lossy().as_ref(),
+            audio_path.to_string_lossy().as_ref(),
+            output_path,
+            &output_format.audio_encoder,
+        )
+        .map_err(ProcessError::Io)?;
+        // Cleanup temp files
+        let _ = std::fs::remove_file(&video_without_audio_path);
+        let _ = std::fs::remove_file(&audio_path);
+    }
+
+    let duration = start_time.elapsed().as_secs_f64();
+
+    Ok(VideoProcessResult {
+        output_path: output_path.to_string(),
+        frames_processed: processed_frames,
+        duration_secs: duration,
+        blurhash: compute_frame_blurhash(output_path),
+    })
+}
+
+/// Minimum frames per segment so tiny clips don't get sliced absurdly thin.
+const MIN_SEGMENT_FRAMES: i32 = 60;
+
+/// Codecs where every frame is coded independently of its neighbors, so
+/// seeking to an arbitrary frame index lands exactly on it instead of the
+/// nearest keyframe. Parallel segment dispatch (and the mid-stream
+/// `CAP_PROP_POS_FRAMES` seeks it relies on) is only frame-accurate for these.
+const ALL_INTRA_CODECS: &[&str] = &["mjpeg", "png", "rawvideo", "ffv1", "prores", "dnxhd", "huffyuv"];
+
+/// Whether `codec` can be safely split into segments seeked to independently
+/// by parallel workers. Long-GOP, inter-coded codecs (h264, h265, vp9, ...)
+/// only seek accurately to keyframes, so a worker starting mid-stream can
+/// land a few frames off its assigned boundary and drop or duplicate frames
+/// at the join - unsafe for anything not on this list.
+fn supports_parallel_seek(codec: &str) -> bool {
+    ALL_INTRA_CODECS.contains(&codec.to_lowercase().as_str())
+}
+
+/// Outcome of inpainting a single segment of frames.
+struct SegmentOutcome {
+    frames_processed: u32,
+}
+
+/// Compute a per-frame scene-change score (mean absolute difference of
+/// downscaled grayscale frames) in a single sequential pass over the input.
+fn compute_scene_scores(input_path: &str, frame_count: i32) -> Result<Vec<f64>, ProcessError> {
+    if frame_count <= 0 {
+        return Ok(Vec::new());
+    }
 
-    if !writer.is_opened().map_err(|e| format!("Failed to check writer: {}", e))? {
-        return Err("Failed to open video writer".to_string());
+    let mut cap = VideoCapture::from_file(input_path, CAP_ANY)
+        .map_err(|e| ProcessError::Io(format!("Failed to open video for scene analysis: {}", e)))?;
+    if !cap.is_opened().map_err(|e| ProcessError::Io(format!("Failed to check video: {}", e)))? {
+        return Err(ProcessError::InvalidInput("Failed to open video file".to_string()));
     }
 
-    // Pre-compute mask and kernel for efficiency
-    let mask = create_mask(info.width, info.height, region)?;
-    let dilated_mask = dilate_mask(&mask, options.dilate_pixels)?;
+    let mut scores = Vec::with_capacity(frame_count as usize);
+    let mut prev_gray: Option<Mat> = None;
+    let mut frame = Mat::default();
+
+    loop {
+        let success = cap
+            .read(&mut frame)
+            .map_err(|e| ProcessError::Io(format!("Failed to read frame: {}", e)))?;
+        if !success || frame.empty() {
+            break;
+        }
+
+        let mut small = Mat::default();
+        imgproc::resize(&frame, &mut small, Size::new(32, 18), 0.0, 0.0, imgproc::INTER_AREA)
+            .map_err(|e| ProcessError::Io(format!("Failed to downscale frame: {}", e)))?;
+        let mut gray = Mat::default();
+        imgproc::cvt_color(&small, &mut gray, imgproc::COLOR_BGR2GRAY, 0)
+            .map_err(|e| ProcessError::Io(format!("Failed to convert frame to grayscale: {}", e)))?;
+
+        let score = if let Some(prev) = &prev_gray {
+            let mut diff = Mat::default();
+            opencv::core::absdiff(prev, &gray, &mut diff)
+                .map_err(|e| ProcessError::Io(format!("Failed to diff frames: {}", e)))?;
+            let mean = opencv::core::mean(&diff, &opencv::core::no_array())
+                .map_err(|e| ProcessError::Io(format!("Failed to score frame: {}", e)))?;
+            mean[0]
+        } else {
+            0.0
+        };
+
+        scores.push(score);
+        prev_gray = Some(gray);
+    }
+
+    Ok(scores)
+}
+
+/// Split `[0, frame_count)` into roughly `workers` segments, snapping each
+/// boundary to the nearest scene cut (a frame whose score exceeds an
+/// adaptive threshold) within a small search window.
+fn compute_segment_boundaries(scores: &[f64], frame_count: i32, workers: usize) -> Vec<(i32, i32)> {
+    if frame_count <= 0 {
+        return vec![(0, 0)];
+    }
+
+    let segment_len = (frame_count / workers as i32).max(MIN_SEGMENT_FRAMES);
+    let mean = scores.iter().sum::<f64>() / scores.len().max(1) as f64;
+    let variance = scores.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / scores.len().max(1) as f64;
+    let threshold = mean + 2.0 * variance.sqrt();
+    let search_window = (segment_len / 4).max(1);
+
+    let mut boundaries = Vec::new();
+    let mut start = 0;
+    while start < frame_count {
+        let mut end = (start + segment_len).min(frame_count);
+        if end < frame_count {
+            let lo = (end - search_window).max(start + MIN_SEGMENT_FRAMES.min(frame_count - start));
+            let hi = (end + search_window).min(frame_count);
+            if lo < hi {
+                if let Some(cut) = (lo..hi)
+                    .filter(|&f| scores.get(f as usize).copied().unwrap_or(0.0) > threshold)
+                    .min_by_key(|&f| (f - end).abs())
+                {
+                    end = cut;
+                }
+            }
+        }
+        boundaries.push((start, end));
+        start = end;
+    }
+
+    boundaries
+}
+
+/// Fallback splitter used when scene analysis fails: fixed frame-count
+/// windows with no cut-snapping.
+fn fixed_window_boundaries(frame_count: i32, workers: usize) -> Vec<(i32, i32)> {
+    if frame_count <= 0 {
+        return vec![(0, 0)];
+    }
+
+    let segment_len = (frame_count / workers as i32).max(MIN_SEGMENT_FRAMES);
+    let mut boundaries = Vec::new();
+    let mut start = 0;
+    while start < frame_count {
+        let end = (start + segment_len).min(frame_count);
+        boundaries.push((start, end));
+        start = end;
+    }
+    boundaries
+}
+
+/// Inpaint one `[start_frame, end_frame)` range of `input_path` into its own
+/// output file. Runs on a worker thread; seeks its own `VideoCapture`
+/// independently of the other segments, and pipes processed frames as raw
+/// `bgr24` into an `ffmpeg` child process over stdin so encoding (including
+/// hardware-accelerated encoders) is handled by ffmpeg rather than OpenCV's
+/// `VideoWriter`.
+#[allow(clippy::too_many_arguments)]
+fn process_segment(
+    input_path: &str,
+    segment_output: &std::path::Path,
+    start_frame: i32,
+    end_frame: i32,
+    fps_num: i32,
+    fps_den: i32,
+    width: i32,
+    height: i32,
+    encoder: &str,
+    output_options: &VideoOutputOptions,
+    mask_spec: &MaskSpec,
+    options: &RemovalOptions,
+) -> Result<SegmentOutcome, ProcessError> {
+    let mut cap = VideoCapture::from_file(input_path, CAP_ANY)
+        .map_err(|e| ProcessError::Io(format!("Failed to open video segment: {}", e)))?;
+    cap.set(videoio::CAP_PROP_POS_FRAMES, start_frame as f64)
+        .map_err(|e| ProcessError::Io(format!("Failed to seek segment: {}", e)))?;
+
+    let mut child = spawn_frame_encoder(segment_output, width, height, fps_num, fps_den, encoder, output_options)
+        .map_err(ProcessError::Io)?;
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| ProcessError::Io("Failed to open encoder stdin".to_string()))?;
+
+    let mask = image_processor::build_mask(width, height, mask_spec)?;
+    let dilated_mask = dilate_mask(&mask, options.dilate_pixels).map_err(ProcessError::Io)?;
 
-    // Select inpainting algorithm
     let inpaint_method = match options.algorithm.to_lowercase().as_str() {
         "navier_stokes" | "ns" => photo::INPAINT_NS,
         _ => photo::INPAINT_TELEA,
     };
 
-    // Process frames
     let mut frame = Mat::default();
-    let mut processed_frames = 0u32;
+    let mut processed = 0u32;
+    let mut encode_error = None;
 
-    loop {
+    for _ in start_frame..end_frame {
         if is_cancelled() {
-            // Cleanup on cancel
-            let _ = std::fs::remove_file(&output_for_writer);
-            if has_audio {
-                let _ = std::fs::remove_file(&audio_path);
-            }
-            return Err("Video processing cancelled".to_string());
+            drop(stdin);
+            let _ = child.wait();
+            return Err(ProcessError::Cancelled);
         }
 
-        let success = cap.read(&mut frame)
-            .map_err(|e| format!("Failed to read frame: {}", e))?;
-
+        let success = cap
+            .read(&mut frame)
+            .map_err(|e| ProcessError::Io(format!("Failed to read frame: {}", e)))?;
         if !success || frame.empty() {
             break;
         }
 
-        // Apply inpainting to this frame
         let mut result = Mat::default();
         photo::inpaint(
             &frame,
@@ -253,57 +964,104 @@ pub fn process_video(
             options.inpaint_radius,
             inpaint_method,
         )
-        .map_err(|e| format!("Inpainting failed at frame {}: {}", processed_frames, e))?;
+        .map_err(|e| ProcessError::Io(format!("Inpainting failed: {}", e)))?;
 
-        // Write processed frame
-        writer.write(&result)
-            .map_err(|e| format!("Failed to write frame {}: {}", processed_frames, e))?;
+        let bytes = result
+            .data_bytes()
+            .map_err(|e| ProcessError::Io(format!("Failed to read frame bytes: {}", e)))?;
 
-        processed_frames += 1;
-        CURRENT_FRAME.store(processed_frames, Ordering::SeqCst);
+        if let Err(e) = stdin.write_all(bytes) {
+            encode_error = Some(ProcessError::Io(format!("Failed to write frame to encoder: {}", e)));
+            break;
+        }
+
+        processed += 1;
+        advance_frame();
     }
 
-    // Release resources
-    drop(writer);
-    drop(cap);
+    drop(stdin);
+    let status = child
+        .wait()
+        .map_err(|e| ProcessError::Io(format!("Failed to wait for encoder: {}", e)))?;
 
-    // Merge audio back if it existed
-    if has_audio {
-        merge_audio(
-            video_without_audio_path.to_string_lossy().as_ref(),
-            audio_path.to_string_lossy().as_ref(),
-            output_path,
-        )?;
-        // Cleanup temp files
-        let _ = std::fs::remove_file(&video_without_audio_path);
-        let _ = std::fs::remove_file(&audio_path);
+    if let Some(e) = encode_error {
+        return Err(e);
     }
 
-    let duration = start_time.elapsed().as_secs_f64();
+    if !status.success() {
+        return Err(ProcessError::Io(format!("ffmpeg encoder exited with {}", status)));
+    }
 
-    Ok(VideoProcessResult {
-        output_path: output_path.to_string(),
-        frames_processed: processed_frames,
-        duration_secs: duration,
-    })
+    Ok(SegmentOutcome { frames_processed: processed })
 }
 
-/// Create binary mask for the watermark region
-fn create_mask(width: i32, height: i32, region: &WatermarkRegion) -> Result<Mat, String> {
-    let mut mask = Mat::zeros(height, width, CV_8UC1)
-        .map_err(|e| format!("Failed to create mask: {}", e))?
-        .to_mat()
-        .map_err(|e| format!("Failed to convert mask: {}", e))?;
+/// Spawn an ffmpeg child process that reads raw `bgr24` frames from stdin
+/// and encodes them to `output_path` using the resolved encoder and the
+/// quality/rate-control knobs from `output_options`.
+fn spawn_frame_encoder(
+    output_path: &std::path::Path,
+    width: i32,
+    height: i32,
+    fps_num: i32,
+    fps_den: i32,
+    encoder: &str,
+    output_options: &VideoOutputOptions,
+) -> Result<std::process::Child, String> {
+    let mut cmd = Command::new("ffmpeg");
+    cmd.args(["-y", "-f", "rawvideo", "-pix_fmt", "bgr24"])
+        .args(["-s", &format!("{}x{}", width, height)])
+        .args(["-r", &format!("{}/{}", fps_num.max(1), fps_den.max(1))])
+        .args(["-i", "-"])
+        .args(["-c:v", encoder]);
 
-    // Fill the region with white (255) in the mask
-    for y in region.y..(region.y + region.height) {
-        for x in region.x..(region.x + region.width) {
-            *mask.at_2d_mut::<u8>(y, x)
-                .map_err(|e| format!("Failed to set mask pixel: {}", e))? = 255;
-        }
+    if let Some(bitrate) = &output_options.bitrate {
+        cmd.args(["-b:v", bitrate]);
+    } else {
+        cmd.args(["-crf", &output_options.crf.to_string()]);
+    }
+
+    if encoder.starts_with("lib") {
+        cmd.args(["-preset", &output_options.preset]);
+    }
+
+    cmd.arg(output_path)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null());
+
+    cmd.spawn().map_err(|e| format!("Failed to spawn ffmpeg encoder: {}", e))
+}
+
+/// Concatenate segment files (already the same codec/container) via
+/// ffmpeg's concat demuxer - a stream copy, no re-encode.
+fn concat_segments(segments: &[std::path::PathBuf], output_path: &str) -> Result<(), String> {
+    let temp_dir = std::env::temp_dir().join("watermark-remover");
+    let list_path = temp_dir.join(format!("concat_{}.txt", std::process::id()));
+
+    let list_contents = segments
+        .iter()
+        .map(|p| format!("file '{}'", p.to_string_lossy().replace('\'', "'\\''")))
+        .collect::<Vec<_>>()
+        .join("\n");
+    std::fs::write(&list_path, list_contents)
+        .map_err(|e| format!("Failed to write concat list: {}", e))?;
+
+    let result = Command::new("ffmpeg")
+        .args(["-y", "-f", "concat", "-safe", "0", "-i"])
+        .arg(&list_path)
+        .args(["-c", "copy", output_path])
+        .output()
+        .map_err(|e| format!("Failed to run ffmpeg concat: {}", e));
+
+    let _ = std::fs::remove_file(&list_path);
+    let result = result?;
+
+    if !result.status.success() {
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        return Err(format!("FFmpeg concat failed: {}", stderr));
     }
 
-    Ok(mask)
+    Ok(())
 }
 
 /// Dilate the mask for better edge blending
@@ -346,14 +1104,25 @@ fn fourcc_to_string(fourcc: i32) -> String {
     String::from_utf8_lossy(&bytes).to_string()
 }
 
-/// Extract audio from video using FFmpeg
-fn extract_audio(video_path: &str, audio_output_path: &str) -> bool {
+/// Map a resolved ffmpeg `-c:a` encoder name to the container extension its
+/// encoded stream is stashed in ahead of the final mux.
+fn audio_file_extension(audio_encoder: &str) -> &'static str {
+    match audio_encoder {
+        "libopus" => "opus",
+        "libvorbis" => "ogg",
+        _ => "aac",
+    }
+}
+
+/// Extract audio from video using FFmpeg. `audio_encoder` must already be
+/// resolved (via `resolve_audio_encoder`) to one the target container accepts.
+fn extract_audio(video_path: &str, audio_output_path: &str, audio_encoder: &str) -> bool {
     let result = Command::new("ffmpeg")
         .args([
             "-y",           // Overwrite output
             "-i", video_path,
             "-vn",          // No video
-            "-acodec", "aac",
+            "-acodec", audio_encoder,
             "-b:a", "192k",
             audio_output_path,
         ])
@@ -365,15 +1134,16 @@ fn extract_audio(video_path: &str, audio_output_path: &str) -> bool {
     }
 }
 
-/// Merge video and audio using FFmpeg
-fn merge_audio(video_path: &str, audio_path: &str, output_path: &str) -> Result<(), String> {
+/// Merge video and audio using FFmpeg. `audio_encoder` must already be
+/// resolved (via `resolve_audio_encoder`) to one the target container accepts.
+fn merge_audio(video_path: &str, audio_path: &str, output_path: &str, audio_encoder: &str) -> Result<(), String> {
     let result = Command::new("ffmpeg")
         .args([
             "-y",           // Overwrite output
             "-i", video_path,
             "-i", audio_path,
             "-c:v", "copy",
-            "-c:a", "aac",
+            "-c:a", audio_encoder,
             "-strict", "experimental",
             output_path,
         ])
@@ -398,4 +1168,119 @@ mod tests {
         let h264 = 0x34363248; // H264
         assert!(!fourcc_to_string(h264).is_empty());
     }
+
+    #[test]
+    fn test_fixed_window_boundaries_covers_whole_range_contiguously() {
+        let boundaries = fixed_window_boundaries(250, 4);
+        assert_eq!(boundaries.first().unwrap().0, 0);
+        assert_eq!(boundaries.last().unwrap().1, 250);
+        for window in boundaries.windows(2) {
+            assert_eq!(window[0].1, window[1].0);
+        }
+    }
+
+    #[test]
+    fn test_fixed_window_boundaries_single_worker_is_one_segment() {
+        assert_eq!(fixed_window_boundaries(250, 1), vec![(0, 250)]);
+    }
+
+    #[test]
+    fn test_fixed_window_boundaries_empty_video() {
+        assert_eq!(fixed_window_boundaries(0, 4), vec![(0, 0)]);
+    }
+
+    #[test]
+    fn test_compute_segment_boundaries_covers_whole_range_contiguously() {
+        let scores = vec![0.0; 250];
+        let boundaries = compute_segment_boundaries(&scores, 250, 4);
+        assert_eq!(boundaries.first().unwrap().0, 0);
+        assert_eq!(boundaries.last().unwrap().1, 250);
+        for window in boundaries.windows(2) {
+            assert_eq!(window[0].1, window[1].0);
+        }
+    }
+
+    #[test]
+    fn test_compute_segment_boundaries_single_worker_is_one_segment() {
+        let scores = vec![0.0; 250];
+        assert_eq!(compute_segment_boundaries(&scores, 250, 1), vec![(0, 250)]);
+    }
+
+    #[test]
+    fn test_supports_parallel_seek() {
+        assert!(supports_parallel_seek("mjpeg"));
+        assert!(supports_parallel_seek("MJPEG"));
+        assert!(!supports_parallel_seek("h264"));
+        assert!(!supports_parallel_seek("vp9"));
+    }
+
+    #[test]
+    fn test_parse_rational_valid() {
+        assert_eq!(parse_rational("30/1"), Some((30, 1)));
+        assert_eq!(parse_rational("30000/1001"), Some((30000, 1001)));
+    }
+
+    #[test]
+    fn test_parse_rational_rejects_degenerate_and_malformed() {
+        assert_eq!(parse_rational("0/0"), None);
+        assert_eq!(parse_rational("30"), None);
+        assert_eq!(parse_rational(""), None);
+        assert_eq!(parse_rational("30/0"), None);
+        assert_eq!(parse_rational("abc/1"), None);
+    }
+
+    fn output_options(container: &str, video_codec: &str, audio_codec: &str) -> VideoOutputOptions {
+        VideoOutputOptions {
+            container: container.to_string(),
+            video_codec: video_codec.to_string(),
+            audio_codec: audio_codec.to_string(),
+            ..VideoOutputOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_resolve_output_format_accepts_known_combinations() {
+        let mp4 = resolve_output_format(&output_options("mp4", "h264", "aac")).unwrap();
+        assert_eq!(mp4.extension, "mp4");
+        assert_eq!(mp4.encoder, "libx264");
+        assert_eq!(mp4.audio_encoder, "aac");
+
+        let webm = resolve_output_format(&output_options("webm", "vp9", "opus")).unwrap();
+        assert_eq!(webm.extension, "webm");
+        assert_eq!(webm.encoder, "libvpx-vp9");
+        assert_eq!(webm.audio_encoder, "libopus");
+    }
+
+    #[test]
+    fn test_resolve_output_format_passes_through_raw_encoder_names() {
+        let resolved = resolve_output_format(&output_options("mp4", "h264_nvenc", "aac")).unwrap();
+        assert_eq!(resolved.encoder, "h264_nvenc");
+    }
+
+    #[test]
+    fn test_resolve_output_format_rejects_unsupported_container() {
+        let err = resolve_output_format(&output_options("gif", "gif", "none")).unwrap_err();
+        assert_eq!(err.error_code(), "invalid_input");
+    }
+
+    #[test]
+    fn test_resolve_output_format_rejects_unsupported_codec_pair() {
+        let err = resolve_output_format(&output_options("webm", "h264", "aac")).unwrap_err();
+        assert_eq!(err.error_code(), "invalid_input");
+    }
+
+    #[test]
+    fn test_resolve_audio_encoder_forces_container_compatible_codec() {
+        // webm never accepts aac, even though it's the default audio codec.
+        assert_eq!(resolve_audio_encoder("webm", "aac").unwrap(), "libopus");
+        assert_eq!(resolve_audio_encoder("webm", "vorbis").unwrap(), "libvorbis");
+        assert_eq!(resolve_audio_encoder("mp4", "opus").unwrap(), "libopus");
+        assert_eq!(resolve_audio_encoder("mp4", "aac").unwrap(), "aac");
+    }
+
+    #[test]
+    fn test_resolve_audio_encoder_none_disables_audio() {
+        assert_eq!(resolve_audio_encoder("mp4", "none").unwrap(), "");
+        assert_eq!(resolve_audio_encoder("webm", "None").unwrap(), "");
+    }
 }